@@ -0,0 +1,452 @@
+//! A compact binary encoding of components, for storing large numbers of them.
+//!
+//! This is not a Minecraft wire format: the game and proxies only understand JSON and NBT.
+//! It's meant for caches, databases and internal messaging, where JSON's repeated field names
+//! and quoting add up.
+//!
+//! The encoding is self-describing, like JSON, so it doesn't depend on the layout of the Rust
+//! types and everything JSON can hold round-trips. An encoded component starts with the
+//! [`MAGIC`] bytes and a [`VERSION`] byte, followed by the value:
+//!
+//! - One tag byte per value: null, false, true, signed integer, unsigned integer, float,
+//!   string, array or object.
+//! - Integers are LEB128 varints, zigzag-encoded when signed. Floats are 8 bytes,
+//!   little-endian. Strings, arrays and objects are prefixed with their varint length.
+//! - Object keys are a varint: `0` is followed by the key as a string, anything else indexes
+//!   a fixed table of the field names components use, so `"color"` takes a single byte.
+//!
+//! Decoding rejects data with another magic or version, so the format can change in later
+//! versions without old data being misread.
+
+use crate::Component;
+use serde_json::{Map, Number, Value};
+use std::fmt;
+
+/// Bytes every encoded component starts with
+pub const MAGIC: [u8; 3] = *b"KCB";
+
+/// Version of the encoding written by [`to_bytes`], stored right after [`MAGIC`]
+pub const VERSION: u8 = 1;
+
+/// Deepest nesting [`from_bytes`] accepts, the same limit `serde_json` applies to JSON text
+const MAX_DEPTH: usize = 128;
+
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_UINT: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_STRING: u8 = 6;
+const TAG_ARRAY: u8 = 7;
+const TAG_OBJECT: u8 = 8;
+
+/// Field names encoded as an index into this table (plus one). Part of the format: entries
+/// can only be appended together with a [`VERSION`] bump.
+const KEYS: [&str; 42] = [
+    "type",
+    "text",
+    "translate",
+    "fallback",
+    "with",
+    "score",
+    "selector",
+    "separator",
+    "keybind",
+    "nbt",
+    "source",
+    "interpret",
+    "block",
+    "entity",
+    "storage",
+    "extra",
+    "color",
+    "font",
+    "bold",
+    "italic",
+    "underlined",
+    "strikethrough",
+    "obfuscated",
+    "shadow_color",
+    "insertion",
+    "click_event",
+    "hover_event",
+    "action",
+    "url",
+    "path",
+    "command",
+    "page",
+    "value",
+    "id",
+    "count",
+    "components",
+    "name",
+    "uuid",
+    "objective",
+    "clickEvent",
+    "hoverEvent",
+    "contents",
+];
+
+/// Error returned when encoding or decoding a component fails
+#[derive(Debug)]
+pub enum BinaryError {
+    /// The data doesn't start with [`MAGIC`]
+    BadMagic,
+    /// The data was encoded with a version this crate can't read
+    UnsupportedVersion(u8),
+    /// The data is truncated or corrupted
+    Malformed(&'static str),
+    /// The decoded value isn't a valid component, or the component couldn't be converted
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::BadMagic => write!(f, "not an encoded component"),
+            BinaryError::UnsupportedVersion(version) => {
+                write!(f, "unsupported encoding version {version}")
+            }
+            BinaryError::Malformed(reason) => write!(f, "malformed encoded component: {reason}"),
+            BinaryError::Json(err) => write!(f, "invalid component: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BinaryError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for BinaryError {
+    fn from(err: serde_json::Error) -> Self {
+        BinaryError::Json(err)
+    }
+}
+
+/// Encodes a component in the binary format.
+///
+/// ```
+/// use kyori_component_json::{Color, Component, NamedColor, binary};
+///
+/// let message = Component::text("Hello").color(Some(Color::Named(NamedColor::Red)));
+/// let bytes = binary::to_bytes(&message).unwrap();
+/// assert!(bytes.len() < serde_json::to_string(&message).unwrap().len());
+/// assert_eq!(binary::from_bytes(&bytes).unwrap(), message);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`BinaryError::Json`] if the component fails to serialize.
+pub fn to_bytes(component: &Component) -> Result<Vec<u8>, BinaryError> {
+    let value = serde_json::to_value(component)?;
+    let mut out = Vec::with_capacity(64);
+    out.extend_from_slice(&MAGIC);
+    out.push(VERSION);
+    write_value(&mut out, &value);
+    Ok(out)
+}
+
+/// Decodes a component written by [`to_bytes`].
+///
+/// # Errors
+///
+/// Returns an error if the header doesn't match, the data is truncated, corrupted or nested
+/// too deeply, or it doesn't hold a valid component.
+pub fn from_bytes(bytes: &[u8]) -> Result<Component, BinaryError> {
+    let rest = bytes.strip_prefix(&MAGIC).ok_or(BinaryError::BadMagic)?;
+    let (&version, rest) = rest
+        .split_first()
+        .ok_or(BinaryError::Malformed("missing version"))?;
+    if version != VERSION {
+        return Err(BinaryError::UnsupportedVersion(version));
+    }
+    let mut reader = Reader { bytes: rest };
+    let value = reader.read_value(0)?;
+    if !reader.bytes.is_empty() {
+        return Err(BinaryError::Malformed("trailing bytes"));
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        out.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn write_str(out: &mut Vec<u8>, s: &str) {
+    write_varint(out, s.len() as u64);
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => out.push(TAG_NULL),
+        Value::Bool(false) => out.push(TAG_FALSE),
+        Value::Bool(true) => out.push(TAG_TRUE),
+        Value::Number(n) => {
+            if let Some(n) = n.as_i64() {
+                out.push(TAG_INT);
+                write_varint(out, ((n << 1) ^ (n >> 63)) as u64);
+            } else if let Some(n) = n.as_u64() {
+                out.push(TAG_UINT);
+                write_varint(out, n);
+            } else {
+                out.push(TAG_FLOAT);
+                out.extend_from_slice(&n.as_f64().unwrap_or_default().to_le_bytes());
+            }
+        }
+        Value::String(s) => {
+            out.push(TAG_STRING);
+            write_str(out, s);
+        }
+        Value::Array(items) => {
+            out.push(TAG_ARRAY);
+            write_varint(out, items.len() as u64);
+            for item in items {
+                write_value(out, item);
+            }
+        }
+        Value::Object(map) => {
+            out.push(TAG_OBJECT);
+            write_varint(out, map.len() as u64);
+            for (key, value) in map {
+                match KEYS.iter().position(|known| known == key) {
+                    Some(index) => write_varint(out, index as u64 + 1),
+                    None => {
+                        out.push(0);
+                        write_str(out, key);
+                    }
+                }
+                write_value(out, value);
+            }
+        }
+    }
+}
+
+/// Cursor over the encoded bytes following the header
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn read_byte(&mut self) -> Result<u8, BinaryError> {
+        let (&byte, rest) = self
+            .bytes
+            .split_first()
+            .ok_or(BinaryError::Malformed("unexpected end of data"))?;
+        self.bytes = rest;
+        Ok(byte)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, BinaryError> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            n |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(BinaryError::Malformed("varint too long"))
+    }
+
+    /// Reads a length prefix, rejecting lengths longer than the remaining data so corrupted
+    /// input can't trigger huge allocations
+    fn read_len(&mut self) -> Result<usize, BinaryError> {
+        usize::try_from(self.read_varint()?)
+            .ok()
+            .filter(|&len| len <= self.bytes.len())
+            .ok_or(BinaryError::Malformed("length exceeds data"))
+    }
+
+    fn read_str(&mut self) -> Result<String, BinaryError> {
+        let len = self.read_len()?;
+        let (s, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        String::from_utf8(s.to_vec()).map_err(|_| BinaryError::Malformed("invalid UTF-8"))
+    }
+
+    fn read_key(&mut self) -> Result<String, BinaryError> {
+        match self.read_varint()? {
+            0 => self.read_str(),
+            index => usize::try_from(index - 1)
+                .ok()
+                .and_then(|index| KEYS.get(index))
+                .map(|key| key.to_string())
+                .ok_or(BinaryError::Malformed("unknown key index")),
+        }
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<Value, BinaryError> {
+        if depth > MAX_DEPTH {
+            return Err(BinaryError::Malformed("nested too deeply"));
+        }
+        Ok(match self.read_byte()? {
+            TAG_NULL => Value::Null,
+            TAG_FALSE => Value::Bool(false),
+            TAG_TRUE => Value::Bool(true),
+            TAG_INT => {
+                let n = self.read_varint()?;
+                Value::from((n >> 1) as i64 ^ -((n & 1) as i64))
+            }
+            TAG_UINT => Value::from(self.read_varint()?),
+            TAG_FLOAT => {
+                let bytes = self
+                    .bytes
+                    .split_first_chunk::<8>()
+                    .ok_or(BinaryError::Malformed("unexpected end of data"))?;
+                let (float, rest) = (f64::from_le_bytes(*bytes.0), bytes.1);
+                self.bytes = rest;
+                Number::from_f64(float)
+                    .map(Value::Number)
+                    .ok_or(BinaryError::Malformed("non-finite float"))?
+            }
+            TAG_STRING => Value::String(self.read_str()?),
+            TAG_ARRAY => {
+                let len = self.read_len()?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.read_value(depth + 1)?);
+                }
+                Value::Array(items)
+            }
+            TAG_OBJECT => {
+                let len = self.read_len()?;
+                let mut map = Map::new();
+                for _ in 0..len {
+                    let key = self.read_key()?;
+                    map.insert(key, self.read_value(depth + 1)?);
+                }
+                Value::Object(map)
+            }
+            _ => return Err(BinaryError::Malformed("unknown value tag")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    fn round_trip(component: &Component) -> Vec<u8> {
+        let bytes = to_bytes(component).unwrap();
+        assert_eq!(&from_bytes(&bytes).unwrap(), component);
+        bytes
+    }
+
+    #[test]
+    fn test_round_trips_every_content_type() {
+        let objects = [
+            ComponentObject {
+                translate: Some("chat.type.text".into()),
+                fallback: Some("<%s> %s".into()),
+                with: Some(vec![Component::from("Steve"), Component::text("hi")]),
+                ..Default::default()
+            },
+            ComponentObject {
+                score: Some(ScoreContent::new("@p", "kills").value("-12")),
+                ..Default::default()
+            },
+            ComponentObject {
+                selector: Some("@a[distance=..5]".into()),
+                separator: Some(Box::new(Component::text(" | "))),
+                ..Default::default()
+            },
+            ComponentObject {
+                keybind: Some("key.jump".into()),
+                ..Default::default()
+            },
+            ComponentObject {
+                nbt: Some("Inventory[0]".into()),
+                source: Some(NbtSource::Entity),
+                entity: Some("@s".into()),
+                interpret: Some(true),
+                ..Default::default()
+            },
+        ];
+        for obj in objects {
+            round_trip(&Component::Object(Box::new(obj)));
+        }
+
+        let styled = Component::text("héllo")
+            .color(Some(Color::Hex("#FF8800".into())))
+            .decoration(TextDecoration::Bold, Some(false))
+            .click_event(Some(ClickEvent::ChangePage { page: -3 }))
+            .hover_event(Some(HoverEvent::ShowEntity {
+                name: Some(Component::text("Zombie")),
+                id: "minecraft:zombie".into(),
+                uuid: UuidRepr::IntArray([1, -2, i32::MAX, i32::MIN]),
+            }))
+            .append(Component::Array(vec!["".into(), "x".into()]));
+        round_trip(&styled);
+        round_trip(&Component::String("plain".into()));
+        round_trip(&Component::text("").hover_event(Some(HoverEvent::ShowItem {
+            id: "minecraft:stone".into(),
+            count: Some(3),
+            components: Some(serde_json::json!({ "custom": [1.5, u64::MAX, null, "x"] })),
+        })));
+    }
+
+    #[test]
+    fn test_smaller_than_json() {
+        let message = Component::text("[")
+            .color(Some(Color::Named(NamedColor::Gray)))
+            .append(
+                Component::text("Server")
+                    .color(Some(Color::Named(NamedColor::Gold)))
+                    .decoration(TextDecoration::Bold, Some(true))
+                    .click_event(Some(ClickEvent::RunCommand {
+                        command: "/server".into(),
+                    })),
+            )
+            .append("] Welcome!");
+        let bytes = round_trip(&message);
+        let json = serde_json::to_vec(&message).unwrap();
+        assert!(
+            bytes.len() * 3 < json.len() * 2,
+            "{} bytes vs {} bytes of JSON",
+            bytes.len(),
+            json.len()
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let bytes = to_bytes(&Component::text("hi")).unwrap();
+        assert!(matches!(from_bytes(b"{}"), Err(BinaryError::BadMagic)));
+
+        let mut newer = bytes.clone();
+        newer[3] = VERSION + 1;
+        assert!(matches!(
+            from_bytes(&newer),
+            Err(BinaryError::UnsupportedVersion(_))
+        ));
+
+        for len in 4..bytes.len() {
+            assert!(matches!(
+                from_bytes(&bytes[..len]),
+                Err(BinaryError::Malformed(_))
+            ));
+        }
+
+        // A huge declared length doesn't allocate, it's just rejected
+        let mut huge = MAGIC.to_vec();
+        huge.extend([VERSION, TAG_ARRAY, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+        assert!(matches!(from_bytes(&huge), Err(BinaryError::Malformed(_))));
+
+        let mut deep = MAGIC.to_vec();
+        deep.push(VERSION);
+        deep.extend([TAG_ARRAY, 1].repeat(MAX_DEPTH + 2));
+        assert!(matches!(from_bytes(&deep), Err(BinaryError::Malformed(_))));
+    }
+}
@@ -36,3 +36,22 @@ pub static NAME_TO_NAMED_COLOR: &[(&str, NamedColor)] = &[
     ("yellow", NamedColor::Yellow),
     ("white", NamedColor::White),
 ];
+#[cfg(feature = "minimessage")]
+pub static LEGACY_CODE_TO_NAMED_COLOR: &[(char, NamedColor)] = &[
+    ('0', NamedColor::Black),
+    ('1', NamedColor::DarkBlue),
+    ('2', NamedColor::DarkGreen),
+    ('3', NamedColor::DarkAqua),
+    ('4', NamedColor::DarkRed),
+    ('5', NamedColor::DarkPurple),
+    ('6', NamedColor::Gold),
+    ('7', NamedColor::Gray),
+    ('8', NamedColor::DarkGray),
+    ('9', NamedColor::Blue),
+    ('a', NamedColor::Green),
+    ('b', NamedColor::Aqua),
+    ('c', NamedColor::Red),
+    ('d', NamedColor::LightPurple),
+    ('e', NamedColor::Yellow),
+    ('f', NamedColor::White),
+];
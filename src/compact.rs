@@ -0,0 +1,336 @@
+//! Simplifying component trees without changing how they render or behave.
+
+use crate::{Component, ComponentObject, ContentType, Style};
+
+/// Checks whether two optional properties can be combined without one overriding the other
+fn agree<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+/// Checks that no property is set to different values on `a` and `b`
+fn styles_agree(a: &Style, b: &Style) -> bool {
+    agree(&a.color, &b.color)
+        && agree(&a.font, &b.font)
+        && agree(&a.bold, &b.bold)
+        && agree(&a.italic, &b.italic)
+        && agree(&a.underlined, &b.underlined)
+        && agree(&a.strikethrough, &b.strikethrough)
+        && agree(&a.obfuscated, &b.obfuscated)
+        && agree(&a.shadow_color, &b.shadow_color)
+        && agree(&a.insertion, &b.insertion)
+        && agree(&a.click_event, &b.click_event)
+        && agree(&a.hover_event, &b.hover_event)
+}
+
+impl ComponentObject {
+    /// Checks whether this object has nothing but (possibly empty) text, style and children
+    fn is_plain_text(&self) -> bool {
+        matches!(self.content_type, None | Some(ContentType::Text))
+            && self.translate.is_none()
+            && self.fallback.is_none()
+            && self.with.is_none()
+            && self.score.is_none()
+            && self.selector.is_none()
+            && self.separator.is_none()
+            && self.keybind.is_none()
+            && self.nbt.is_none()
+            && self.source.is_none()
+            && self.interpret.is_none()
+            && self.block.is_none()
+            && self.entity.is_none()
+            && self.storage.is_none()
+    }
+
+    /// Style of this object if it is a childless plain text node
+    fn as_text_leaf_style(&self) -> Option<Style> {
+        (self.is_plain_text() && self.extra.is_none()).then(|| self.style())
+    }
+}
+
+impl Component {
+    /// The text and style of a childless plain text node, the only kind of node that can be
+    /// merged with its siblings
+    fn as_text_leaf(&self) -> Option<(&str, Style)> {
+        match self {
+            Component::String(text) => Some((text, Style::default())),
+            Component::Object(obj) => obj
+                .as_text_leaf_style()
+                .map(|style| (obj.text.as_deref().unwrap_or_default(), style)),
+            Component::Array(_) => None,
+        }
+    }
+
+    /// Simplifies the tree while keeping its rendering and interactivity identical:
+    ///
+    /// - Adjacent childless text siblings are merged when their styles are exactly equal.
+    ///   Insertion, click and hover events are part of the style, so text is never merged
+    ///   across different events, even when it looks the same.
+    /// - Childless text siblings with empty text are dropped, as they show nothing and can't
+    ///   be clicked or hovered. The first element of an array is kept, since it's the parent
+    ///   of the others.
+    /// - A text node without text of its own and a single child is collapsed into that child,
+    ///   which takes the parent's style for the properties it doesn't set. This only happens
+    ///   when no property, events included, is set to different values on the two; otherwise
+    ///   the parent is kept. The first element of an array with other elements is never
+    ///   collapsed, as the others inherit its style and would pick up the child's.
+    /// - An array with a single element is replaced by that element.
+    ///
+    /// Translation arguments, separators and hover text are compacted too.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("").color(Some(Color::Named(NamedColor::Red))).append(
+    ///     Component::text("Hello, ").append("world").append(Component::text("")),
+    /// );
+    /// assert_eq!(
+    ///     message.compact(),
+    ///     Component::text("Hello, ")
+    ///         .color(Some(Color::Named(NamedColor::Red)))
+    ///         .append("world")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn compact(self) -> Self {
+        self.compact_with(true)
+    }
+
+    /// Compacts this component, collapsing its root into its single child only if `collapse`
+    fn compact_with(self, collapse: bool) -> Self {
+        match self {
+            Component::Array(children) => {
+                let has_siblings = children.len() > 1;
+                let mut children = children.into_iter();
+                let Some(first) = children.next() else {
+                    return Component::Array(Vec::new());
+                };
+                // The other elements inherit the first one's style, so collapsing it would
+                // hand them its child's style as well
+                let mut compacted = vec![first.compact_with(!has_siblings)];
+                compacted.extend(merge_siblings(children.map(Component::compact).collect()));
+                if compacted.len() == 1
+                    && let Some(only) = compacted.pop()
+                {
+                    return match only {
+                        Component::Object(obj) => collapse_single_child(*obj),
+                        only => only,
+                    };
+                }
+                Component::Array(compacted)
+            }
+            Component::Object(mut obj) => {
+                for child in obj.nested_mut() {
+                    let taken = std::mem::replace(child, Component::String(String::new()));
+                    *child = taken.compact();
+                }
+                if let Some(extra) = obj.extra.take() {
+                    let extra = merge_siblings(extra);
+                    obj.extra = (!extra.is_empty()).then_some(extra);
+                }
+                if collapse {
+                    collapse_single_child(*obj)
+                } else {
+                    Component::Object(obj)
+                }
+            }
+            string => string,
+        }
+    }
+}
+
+/// Merges adjacent text leaves with equal styles and drops empty ones
+fn merge_siblings(children: Vec<Component>) -> Vec<Component> {
+    let mut merged: Vec<Component> = Vec::with_capacity(children.len());
+    for child in children {
+        let Some((text, style)) = child.as_text_leaf() else {
+            merged.push(child);
+            continue;
+        };
+        if text.is_empty() {
+            continue;
+        }
+        match merged.last_mut() {
+            Some(Component::String(last)) if style == Style::default() => last.push_str(text),
+            Some(Component::Object(last))
+                if last.as_text_leaf_style().is_some_and(|last| last == style) =>
+            {
+                last.text.get_or_insert_default().push_str(text);
+            }
+            _ => merged.push(child),
+        }
+    }
+    merged
+}
+
+/// Replaces a text node with no text of its own and a single child by that child, when
+/// their styles don't conflict
+fn collapse_single_child(mut parent: ComponentObject) -> Component {
+    let collapsible = parent.is_plain_text()
+        && parent.text.as_deref().is_none_or(str::is_empty)
+        && matches!(
+            parent.extra.as_deref(),
+            Some([Component::String(_) | Component::Object(_)])
+        );
+    if !collapsible {
+        return Component::Object(Box::new(parent));
+    }
+    let Some(child) = parent.extra.take().and_then(|mut extra| extra.pop()) else {
+        return Component::Object(Box::new(parent));
+    };
+
+    let mut child = child.into_object();
+    let parent_style = parent.style();
+    if !styles_agree(&child.style(), &parent_style) {
+        parent.extra = Some(vec![Component::Object(Box::new(child))]);
+        return Component::Object(Box::new(parent));
+    }
+    child.merge_style(&parent_style);
+    Component::Object(Box::new(child))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn red() -> Option<Color> {
+        Some(Color::Named(NamedColor::Red))
+    }
+
+    fn run(command: &str) -> Option<ClickEvent> {
+        Some(ClickEvent::RunCommand {
+            command: command.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_merges_equal_siblings_only() {
+        let message = Component::text("root").set_children(vec![
+            Component::from("a"),
+            Component::from("b"),
+            Component::text("c").color(red()),
+            Component::text("d").color(red()),
+            Component::text("").click_event(run("/x")),
+            Component::text("e").color(red()).click_event(run("/x")),
+            Component::text("f").color(red()).click_event(run("/y")),
+            Component::text("g")
+                .color(red())
+                .insertion(Some("ins".into())),
+        ]);
+        assert_eq!(
+            message.compact(),
+            Component::text("root").set_children(vec![
+                Component::from("ab"),
+                Component::text("cd").color(red()),
+                Component::text("e").color(red()).click_event(run("/x")),
+                Component::text("f").color(red()).click_event(run("/y")),
+                Component::text("g")
+                    .color(red())
+                    .insertion(Some("ins".into())),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_never_merges_nodes_with_children_or_other_content() {
+        let keybind = Component::Object(Box::new(ComponentObject {
+            keybind: Some("key.jump".into()),
+            ..Default::default()
+        }));
+        let message = Component::text("root").set_children(vec![
+            Component::text("a").append(Component::text("child").click_event(run("/x"))),
+            Component::text("b"),
+            keybind.clone(),
+            Component::text("c"),
+        ]);
+        assert_eq!(message.clone().compact(), message);
+    }
+
+    #[test]
+    fn test_collapses_style_only_parent() {
+        let hover = Some(HoverEvent::ShowText {
+            value: Component::text("tip"),
+        });
+        let parent = Component::text("")
+            .color(red())
+            .hover_event(hover.clone())
+            .append(Component::text("x").click_event(run("/x")));
+        assert_eq!(
+            parent.compact(),
+            Component::text("x")
+                .color(red())
+                .hover_event(hover)
+                .click_event(run("/x"))
+        );
+    }
+
+    #[test]
+    fn test_keeps_first_array_element_with_siblings() {
+        let json = r#"[{"text":"","color":"red","extra":[{"text":"x","click_event":{"action":"run_command","command":"/x"}}]},"after"]"#;
+        let message: Component = serde_json::from_str(json).unwrap();
+        let compacted = message.clone().compact();
+        assert_eq!(compacted, message);
+        assert!(compacted.iter_text().eq(message.iter_text()));
+
+        // Alone in its array, the element can collapse
+        let alone = Component::Array(vec![
+            Component::text("")
+                .color(red())
+                .append(Component::text("x").click_event(run("/x"))),
+            Component::text(""),
+        ]);
+        assert_eq!(
+            alone.compact(),
+            Component::text("x").color(red()).click_event(run("/x"))
+        );
+    }
+
+    #[test]
+    fn test_keeps_parent_when_child_overrides_it() {
+        // The child's click event differs from the parent's
+        let events = Component::text("")
+            .click_event(run("/parent"))
+            .append(Component::text("x").click_event(run("/child")));
+        assert_eq!(events.clone().compact(), events);
+
+        // The parent has text of its own
+        let with_text = Component::text("a").append(Component::text("b").color(red()));
+        assert_eq!(with_text.clone().compact(), with_text);
+
+        // Two children
+        let two = Component::text("")
+            .color(red())
+            .append("a")
+            .append(Component::text("b").click_event(run("/x")));
+        assert_eq!(two.clone().compact(), two);
+    }
+
+    #[test]
+    fn test_compacts_arrays_and_nested_components() {
+        let array = Component::Array(vec![
+            Component::text("").color(red()),
+            Component::from("a"),
+            Component::text(""),
+            Component::from("b"),
+        ]);
+        assert_eq!(
+            array.compact(),
+            Component::Array(vec![
+                Component::text("").color(red()),
+                Component::from("ab")
+            ])
+        );
+
+        let hover = Component::text("x").hover_text(Component::text("").append("tip"));
+        assert_eq!(
+            hover.compact(),
+            Component::text("x").hover_text(Component::text("tip"))
+        );
+        assert_eq!(
+            Component::Array(vec![Component::text("solo")]).compact(),
+            Component::text("solo")
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! A readable tree view of components, for debugging.
+
+use crate::{ClickEvent, Component, ComponentObject, ContentType, HoverEvent};
+use serde_json::Value;
+
+impl Component {
+    /// Renders this component as an indented tree, one node per line: its content followed by
+    /// a summary of the style set directly on it. Easier to read than `{:#?}` when checking
+    /// what a large component looks like.
+    ///
+    /// Children are indented under their parent. Translation arguments, separators and hover
+    /// text are listed first, labeled `with:`, `separator:` and `hover:`, then `extra`
+    /// children. Decorations explicitly turned off are prefixed with `!`.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("Hello")
+    ///     .color(Some(Color::Named(NamedColor::Yellow)))
+    ///     .decoration(TextDecoration::Bold, Some(true))
+    ///     .append(Component::text(" world").decoration(TextDecoration::Bold, Some(false)));
+    /// assert_eq!(
+    ///     message.debug_tree(),
+    ///     "Text(\"Hello\") [yellow, bold]\n  Text(\" world\") [!bold]"
+    /// );
+    /// ```
+    pub fn debug_tree(&self) -> String {
+        let mut lines = Vec::new();
+        self.debug_lines("", 0, &mut lines);
+        lines.join("\n")
+    }
+
+    fn debug_lines(&self, label: &str, indent: usize, lines: &mut Vec<String>) {
+        let prefix = format!("{}{label}", "  ".repeat(indent));
+        match self {
+            Component::String(text) => lines.push(format!("{prefix}String({text:?})")),
+            Component::Array(children) => {
+                lines.push(format!("{prefix}Array"));
+                for child in children {
+                    child.debug_lines("", indent + 1, lines);
+                }
+            }
+            Component::Object(obj) => {
+                let style = obj.style_summary();
+                if style.is_empty() {
+                    lines.push(format!("{prefix}{}", obj.content_summary()));
+                } else {
+                    lines.push(format!(
+                        "{prefix}{} [{}]",
+                        obj.content_summary(),
+                        style.join(", ")
+                    ));
+                }
+                for arg in obj.with.iter().flatten() {
+                    arg.debug_lines("with: ", indent + 1, lines);
+                }
+                if let Some(separator) = &obj.separator {
+                    separator.debug_lines("separator: ", indent + 1, lines);
+                }
+                match &obj.hover_event {
+                    Some(HoverEvent::ShowText { value })
+                    | Some(HoverEvent::ShowEntity {
+                        name: Some(value), ..
+                    }) => value.debug_lines("hover: ", indent + 1, lines),
+                    _ => {}
+                }
+                for child in obj.extra.iter().flatten() {
+                    child.debug_lines("", indent + 1, lines);
+                }
+            }
+        }
+    }
+}
+
+impl ComponentObject {
+    /// The content Minecraft shows for this object, e.g. `Text("Hello")`
+    fn content_summary(&self) -> String {
+        let field = |value: &Option<String>| value.as_deref().unwrap_or_default().to_string();
+        match self.inferred_content_type() {
+            Some(ContentType::Text) => format!("Text({:?})", field(&self.text)),
+            Some(ContentType::Translatable) => match &self.fallback {
+                Some(fallback) => format!(
+                    "Translate({:?}, fallback: {fallback:?})",
+                    field(&self.translate)
+                ),
+                None => format!("Translate({:?})", field(&self.translate)),
+            },
+            Some(ContentType::Score) => match &self.score {
+                Some(score) => format!("Score({:?}, {:?})", score.name, score.objective),
+                None => "Score".to_string(),
+            },
+            Some(ContentType::Selector) => format!("Selector({:?})", field(&self.selector)),
+            Some(ContentType::Keybind) => format!("Keybind({:?})", field(&self.keybind)),
+            Some(ContentType::Nbt) => format!("Nbt({:?})", field(&self.nbt)),
+            None => "Empty".to_string(),
+        }
+    }
+
+    /// The style properties set on this object, in a short human-readable form
+    fn style_summary(&self) -> Vec<String> {
+        let mut summary = Vec::new();
+        if let Some(color) = &self.color {
+            summary.push(color.to_string());
+        }
+        for (name, state) in [
+            ("bold", self.bold),
+            ("italic", self.italic),
+            ("underlined", self.underlined),
+            ("strikethrough", self.strikethrough),
+            ("obfuscated", self.obfuscated),
+        ] {
+            match state {
+                Some(true) => summary.push(name.to_string()),
+                Some(false) => summary.push(format!("!{name}")),
+                None => {}
+            }
+        }
+        if let Some(font) = &self.font {
+            summary.push(format!("font={font}"));
+        }
+        if let Some(shadow) = &self.shadow_color {
+            let [r, g, b, a] = shadow.to_rgba();
+            summary.push(format!("shadow=#{r:02X}{g:02X}{b:02X}{a:02X}"));
+        }
+        if let Some(event) = &self.click_event {
+            let value = match event {
+                ClickEvent::OpenUrl { url } => url.clone(),
+                ClickEvent::OpenFile { path } => path.clone(),
+                ClickEvent::RunCommand { command } | ClickEvent::SuggestCommand { command } => {
+                    command.clone()
+                }
+                ClickEvent::ChangePage { page } => page.to_string(),
+                ClickEvent::CopyToClipboard { value } => value.clone(),
+                ClickEvent::ShowDialog { dialog } => dialog.to_string(),
+                ClickEvent::Custom { id, .. } => id.clone(),
+                ClickEvent::Other(event) => {
+                    let mut fields = event.clone();
+                    fields.remove("action");
+                    Value::Object(fields).to_string()
+                }
+            };
+            summary.push(format!("click={}:{value}", event.action()));
+        }
+        if let Some(event) = &self.hover_event {
+            summary.push(format!("hover={}", event.action()));
+        }
+        if let Some(insertion) = &self.insertion {
+            summary.push(format!("insertion={insertion:?}"));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_debug_tree_shape() {
+        let message = Component::Array(vec![
+            Component::text("Welcome ")
+                .color(Some(Color::Hex("#FF8800".into())))
+                .font(Some("minecraft:uniform".into())),
+            Component::translatable_with_fallback("chat.type.text", "<%s> %s")
+                .decoration(TextDecoration::Italic, Some(false))
+                .click_run("/spawn")
+                .hover_text("Go home")
+                .with_arg("Steve")
+                .with_arg(Component::keybind("key.jump")),
+            "plain".into(),
+        ]);
+        assert_eq!(
+            message.debug_tree(),
+            [
+                "Array",
+                "  Text(\"Welcome \") [#FF8800, font=minecraft:uniform]",
+                "  Translate(\"chat.type.text\", fallback: \"<%s> %s\") \
+                 [!italic, click=run_command:/spawn, hover=show_text]",
+                "    with: String(\"Steve\")",
+                "    with: Keybind(\"key.jump\")",
+                "    hover: String(\"Go home\")",
+                "  String(\"plain\")",
+            ]
+            .join("\n")
+        );
+        assert_eq!(Component::Object(Box::default()).debug_tree(), "Empty");
+    }
+}
@@ -0,0 +1,202 @@
+//! Compact storage for the five text decorations.
+
+use crate::{Component, TextDecoration};
+use std::collections::HashMap;
+
+const ALL_DECORATIONS: [TextDecoration; 5] = [
+    TextDecoration::Bold,
+    TextDecoration::Italic,
+    TextDecoration::Underlined,
+    TextDecoration::Strikethrough,
+    TextDecoration::Obfuscated,
+];
+
+/// The state of all five decorations, packed into two bytes.
+///
+/// Each decoration is tri-state, mirroring the `Option<bool>` fields of a component:
+///
+/// | `set` bit | `enabled` bit | state                               |
+/// |-----------|---------------|-------------------------------------|
+/// | 0         | 0             | unset (`None`), inherited           |
+/// | 1         | 1             | explicitly enabled (`Some(true)`)   |
+/// | 1         | 0             | explicitly disabled (`Some(false)`) |
+///
+/// An `enabled` bit is never set without its `set` bit.
+///
+/// ```
+/// use kyori_component_json::*;
+///
+/// let set = DecorationSet::new()
+///     .with(TextDecoration::Bold, Some(true))
+///     .with(TextDecoration::Italic, Some(false));
+/// let component = Component::text("Hi").with_decorations(set);
+///
+/// assert!(component.has_decoration(TextDecoration::Bold));
+/// assert_eq!(component.decoration_set().get(TextDecoration::Italic), Some(false));
+/// assert_eq!(component.decoration_set().get(TextDecoration::Underlined), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DecorationSet {
+    set: u8,
+    enabled: u8,
+}
+
+impl DecorationSet {
+    /// Creates a set with every decoration unset
+    pub const fn new() -> Self {
+        DecorationSet { set: 0, enabled: 0 }
+    }
+
+    const fn bit(decoration: TextDecoration) -> u8 {
+        1 << decoration as u8
+    }
+
+    /// Gets the state of `decoration`: `None` when unset
+    pub const fn get(&self, decoration: TextDecoration) -> Option<bool> {
+        let bit = Self::bit(decoration);
+        if self.set & bit == 0 {
+            None
+        } else {
+            Some(self.enabled & bit != 0)
+        }
+    }
+
+    /// Sets the state of `decoration`; `None` unsets it
+    pub const fn set(&mut self, decoration: TextDecoration, state: Option<bool>) {
+        let bit = Self::bit(decoration);
+        match state {
+            None => {
+                self.set &= !bit;
+                self.enabled &= !bit;
+            }
+            Some(enabled) => {
+                self.set |= bit;
+                if enabled {
+                    self.enabled |= bit;
+                } else {
+                    self.enabled &= !bit;
+                }
+            }
+        }
+    }
+
+    /// Returns this set with the state of `decoration` changed
+    #[must_use]
+    pub const fn with(mut self, decoration: TextDecoration, state: Option<bool>) -> Self {
+        self.set(decoration, state);
+        self
+    }
+
+    /// Checks whether no decoration is set
+    pub const fn is_empty(&self) -> bool {
+        self.set == 0
+    }
+
+    /// Iterates over the decorations that are set, with their state
+    pub fn iter(&self) -> impl Iterator<Item = (TextDecoration, bool)> + '_ {
+        ALL_DECORATIONS
+            .into_iter()
+            .filter_map(|decoration| self.get(decoration).map(|state| (decoration, state)))
+    }
+}
+
+impl From<&HashMap<TextDecoration, Option<bool>>> for DecorationSet {
+    fn from(map: &HashMap<TextDecoration, Option<bool>>) -> Self {
+        map.iter()
+            .fold(DecorationSet::new(), |set, (&decoration, &state)| {
+                set.with(decoration, state)
+            })
+    }
+}
+
+/// Lists all five decorations, with `None` for the unset ones, so passing the map to
+/// [`Component::decorations`] has the same effect as [`Component::with_decorations`].
+impl From<DecorationSet> for HashMap<TextDecoration, Option<bool>> {
+    fn from(set: DecorationSet) -> Self {
+        ALL_DECORATIONS
+            .into_iter()
+            .map(|decoration| (decoration, set.get(decoration)))
+            .collect()
+    }
+}
+
+impl Component {
+    /// Gets the decorations set directly on this component. Plain strings and arrays have none.
+    pub fn decoration_set(&self) -> DecorationSet {
+        match self {
+            Component::Object(obj) => DecorationSet::new()
+                .with(TextDecoration::Bold, obj.bold)
+                .with(TextDecoration::Italic, obj.italic)
+                .with(TextDecoration::Underlined, obj.underlined)
+                .with(TextDecoration::Strikethrough, obj.strikethrough)
+                .with(TextDecoration::Obfuscated, obj.obfuscated),
+            _ => DecorationSet::new(),
+        }
+    }
+
+    /// Replaces all five decorations with the states in `set`, unsetting those it leaves unset
+    #[must_use]
+    pub fn with_decorations(self, set: DecorationSet) -> Self {
+        self.map_object(|mut obj| {
+            obj.bold = set.get(TextDecoration::Bold);
+            obj.italic = set.get(TextDecoration::Italic);
+            obj.underlined = set.get(TextDecoration::Underlined);
+            obj.strikethrough = set.get(TextDecoration::Strikethrough);
+            obj.obfuscated = set.get(TextDecoration::Obfuscated);
+            obj
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_tri_state() {
+        let mut set = DecorationSet::new();
+        assert!(set.is_empty());
+        set.set(TextDecoration::Bold, Some(true));
+        set.set(TextDecoration::Italic, Some(false));
+        assert_eq!(set.get(TextDecoration::Bold), Some(true));
+        assert_eq!(set.get(TextDecoration::Italic), Some(false));
+        assert_eq!(set.get(TextDecoration::Obfuscated), None);
+
+        set.set(TextDecoration::Bold, None);
+        assert_eq!(set.get(TextDecoration::Bold), None);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [(TextDecoration::Italic, false)]
+        );
+        // Unsetting clears the enabled bit too, so equal states compare equal
+        assert_eq!(
+            set,
+            DecorationSet::new().with(TextDecoration::Italic, Some(false))
+        );
+    }
+
+    #[test]
+    fn test_hash_map_conversions() {
+        let map = HashMap::from([
+            (TextDecoration::Underlined, Some(true)),
+            (TextDecoration::Strikethrough, Some(false)),
+            (TextDecoration::Bold, None),
+        ]);
+        let set = DecorationSet::from(&map);
+        assert_eq!(set.get(TextDecoration::Underlined), Some(true));
+        assert_eq!(set.get(TextDecoration::Strikethrough), Some(false));
+        assert_eq!(set.get(TextDecoration::Bold), None);
+
+        let back: HashMap<_, _> = set.into();
+        assert_eq!(back.len(), 5);
+        assert_eq!(DecorationSet::from(&back), set);
+
+        let base = Component::text("x").decoration(TextDecoration::Bold, Some(true));
+        assert_eq!(
+            base.clone().decorations(&back),
+            base.clone().with_decorations(set)
+        );
+        assert_eq!(base.with_decorations(set).decoration_set(), set);
+    }
+}
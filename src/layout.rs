@@ -0,0 +1,726 @@
+//! Width-aware text layout, such as wrapping text onto sign lines.
+
+use crate::{Component, EMPTY_STYLE, Style};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Advance widths, in pixels, of the printable ASCII glyphs of Minecraft's default font.
+///
+/// Each width includes the 1px gap the game draws after every glyph.
+static VANILLA_ASCII_WIDTHS: &[(char, u32)] = &[
+    (' ', 4),
+    ('!', 2),
+    ('"', 4),
+    ('\'', 2),
+    ('(', 5),
+    (')', 5),
+    ('*', 5),
+    (',', 2),
+    ('.', 2),
+    (':', 2),
+    (';', 2),
+    ('<', 5),
+    ('>', 5),
+    ('@', 7),
+    ('I', 4),
+    ('[', 4),
+    (']', 4),
+    ('`', 3),
+    ('f', 5),
+    ('i', 2),
+    ('k', 5),
+    ('l', 3),
+    ('t', 4),
+    ('{', 5),
+    ('|', 2),
+    ('}', 5),
+];
+
+/// Checks whether `c` is an East Asian wide or fullwidth character, which takes up two
+/// columns in a terminal. Covers the common CJK, Hangul, fullwidth and emoji blocks.
+fn is_wide(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1100}'..='\u{115F}'
+            | '\u{2E80}'..='\u{303E}'
+            | '\u{3041}'..='\u{33FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{A000}'..='\u{A4CF}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{1F300}'..='\u{1F64F}'
+            | '\u{1F900}'..='\u{1F9FF}'
+            | '\u{20000}'..='\u{2FFFD}'
+            | '\u{30000}'..='\u{3FFFD}'
+    )
+}
+
+/// Per-glyph advance widths used to measure rendered text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontMetrics {
+    widths: HashMap<char, u32>,
+    default_width: u32,
+    wide_width: u32,
+    bold_extra: u32,
+}
+
+impl FontMetrics {
+    /// Creates metrics where every glyph advances by `default_width` pixels, and one pixel
+    /// more when bold.
+    pub fn new(default_width: u32) -> Self {
+        FontMetrics {
+            widths: HashMap::new(),
+            default_width,
+            wide_width: default_width,
+            bold_extra: 1,
+        }
+    }
+
+    /// Metrics of Minecraft's default font for ASCII text.
+    ///
+    /// Most glyphs advance 6px; narrower glyphs such as `i`, `l` and `.` are listed
+    /// individually. Wide CJK glyphs, drawn from the half-scale unifont, advance 9px, and
+    /// other characters outside ASCII use the 6px default.
+    pub fn vanilla_default() -> Self {
+        VANILLA_ASCII_WIDTHS
+            .iter()
+            .fold(FontMetrics::new(6), |metrics, &(c, width)| {
+                metrics.with_width(c, width)
+            })
+            .with_wide_width(9)
+    }
+
+    /// Metrics counting terminal columns: one per character, two for wide CJK characters,
+    /// and no extra width for bold text.
+    pub fn monospace() -> Self {
+        FontMetrics {
+            bold_extra: 0,
+            ..FontMetrics::new(1)
+        }
+        .with_wide_width(2)
+    }
+
+    /// Reads the glyph widths defined by a resource pack font, the JSON found at
+    /// `assets/<namespace>/font/<name>.json`.
+    ///
+    /// Supported provider types:
+    ///
+    /// - `space`: each character advances by its listed width, rounded to whole pixels.
+    ///   Negative advances count as zero.
+    /// - `bitmap`: the texture isn't available here, so every glyph is assumed to fill a
+    ///   square cell. It advances by the provider's `height` (8 by default) plus the 1px
+    ///   gap. `\0` entries in `chars` are empty cells and define nothing.
+    ///
+    /// Other providers (`ttf`, `unihex`, `reference`, ...) are skipped. As in the game, the
+    /// first provider defining a character wins, and characters that no provider defines
+    /// use a 6px default. Use [`Self::with_width`] to correct individual glyphs.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let font = r#"{"providers": [
+    ///     {"type": "space", "advances": {" ": 4}},
+    ///     {"type": "bitmap", "file": "custom:font/icons.png", "ascent": 7, "height": 9, "chars": ["\uE000"]}
+    /// ]}"#;
+    /// let metrics = FontMetrics::from_font_provider(font).unwrap();
+    /// assert_eq!(metrics.char_width(' ', false), 4);
+    /// assert_eq!(metrics.char_width('\u{E000}', false), 10);
+    /// ```
+    pub fn from_font_provider(json: &str) -> Result<Self, FontProviderError> {
+        let definition: FontDefinition = serde_json::from_str(json)?;
+        let mut metrics = FontMetrics::new(6);
+        for provider in definition.providers {
+            match provider {
+                GlyphProvider::Bitmap {
+                    height,
+                    ascent,
+                    chars,
+                } => {
+                    if ascent > height {
+                        return Err(FontProviderError::AscentAboveHeight { ascent, height });
+                    }
+                    let row_len = chars.first().map_or(0, |row| row.chars().count());
+                    if chars.iter().any(|row| row.chars().count() != row_len) {
+                        return Err(FontProviderError::UnevenRows);
+                    }
+                    let width = u32::try_from(height).unwrap_or(0) + 1;
+                    for c in chars.iter().flat_map(|row| row.chars()) {
+                        if c != '\0' {
+                            metrics.widths.entry(c).or_insert(width);
+                        }
+                    }
+                }
+                GlyphProvider::Space { advances } => {
+                    for (c, advance) in advances {
+                        // Saturating float to int conversion turns negative advances into 0
+                        metrics.widths.entry(c).or_insert(advance.round() as u32);
+                    }
+                }
+                GlyphProvider::Unsupported => {}
+            }
+        }
+        Ok(metrics)
+    }
+
+    /// Sets the advance width of East Asian wide and fullwidth characters that have no width
+    /// of their own.
+    #[must_use]
+    pub fn with_wide_width(mut self, width: u32) -> Self {
+        self.wide_width = width;
+        self
+    }
+
+    /// Overrides the advance width of a single character.
+    #[must_use]
+    pub fn with_width(mut self, c: char, width: u32) -> Self {
+        self.widths.insert(c, width);
+        self
+    }
+
+    /// Advance width of `c`, wider when bold (by one pixel unless these are
+    /// [`Self::monospace`] metrics).
+    pub fn char_width(&self, c: char, bold: bool) -> u32 {
+        let width = match self.widths.get(&c) {
+            Some(&width) => width,
+            None if is_wide(c) => self.wide_width,
+            None => self.default_width,
+        };
+        if bold { width + self.bold_extra } else { width }
+    }
+}
+
+/// A resource pack font definition, `assets/<namespace>/font/<name>.json`
+#[derive(Deserialize)]
+struct FontDefinition {
+    providers: Vec<GlyphProvider>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum GlyphProvider {
+    Bitmap {
+        #[serde(default = "default_bitmap_height")]
+        height: i32,
+        ascent: i32,
+        chars: Vec<String>,
+    },
+    Space {
+        advances: HashMap<char, f32>,
+    },
+    #[serde(other)]
+    Unsupported,
+}
+
+fn default_bitmap_height() -> i32 {
+    8
+}
+
+/// Error returned by [`FontMetrics::from_font_provider`]
+#[derive(Debug)]
+pub enum FontProviderError {
+    /// The font definition isn't valid JSON or doesn't have the expected shape
+    Json(serde_json::Error),
+    /// A bitmap provider's `ascent` is greater than its `height`
+    AscentAboveHeight {
+        /// The provider's `ascent`
+        ascent: i32,
+        /// The provider's `height`
+        height: i32,
+    },
+    /// The rows of a bitmap provider's `chars` don't all have the same number of glyphs
+    UnevenRows,
+}
+
+impl fmt::Display for FontProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontProviderError::Json(err) => write!(f, "invalid font definition: {err}"),
+            FontProviderError::AscentAboveHeight { ascent, height } => {
+                write!(
+                    f,
+                    "bitmap ascent {ascent} is higher than its height {height}"
+                )
+            }
+            FontProviderError::UnevenRows => {
+                write!(f, "bitmap chars rows have different lengths")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FontProviderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FontProviderError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for FontProviderError {
+    fn from(err: serde_json::Error) -> Self {
+        FontProviderError::Json(err)
+    }
+}
+
+/// A line under construction: styled pieces plus the width they take up
+#[derive(Default)]
+struct Line {
+    pieces: Vec<(String, Style)>,
+    width: u32,
+}
+
+impl Line {
+    fn push(&mut self, c: char, style: &Style, width: u32) {
+        match self.pieces.last_mut() {
+            Some((text, last)) if *last == *style => text.push(c),
+            _ => self.pieces.push((c.to_string(), style.clone())),
+        }
+        self.width += width;
+    }
+
+    fn into_component(self) -> Component {
+        let mut pieces = self
+            .pieces
+            .into_iter()
+            .map(|(text, style)| Component::text(text).apply_fallback_style(&style));
+        match (pieces.next(), pieces.len()) {
+            (None, _) => Component::text(""),
+            (Some(piece), 0) => piece,
+            // An unstyled root keeps the pieces from inheriting each other's styles
+            (Some(piece), _) => {
+                Component::text("").set_children(std::iter::once(piece).chain(pieces).collect())
+            }
+        }
+    }
+}
+
+impl Component {
+    /// Measures the resolved text of this component with `metrics`: the width of its widest
+    /// line, in pixels or, with [`FontMetrics::monospace`], in terminal columns.
+    ///
+    /// Bold text, including inherited bold, is measured with its extra width. `\n` starts a
+    /// new line, and legacy `§` formatting codes are skipped since the client doesn't draw
+    /// them.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let text = Component::text("Hi ").append("世界");
+    /// assert_eq!(text.display_width(&FontMetrics::monospace()), 7);
+    /// assert_eq!(text.display_width(&FontMetrics::vanilla_default()), 6 + 2 + 4 + 9 + 9);
+    /// ```
+    pub fn display_width(&self, metrics: &FontMetrics) -> u32 {
+        let mut widest = 0;
+        let mut line = 0;
+        for (text, style) in self.styled_runs() {
+            let bold = style.bold == Some(true);
+            // A code is read within one text, so a trailing `§` doesn't hide the next run's
+            let mut after_section_sign = false;
+            for c in text.chars() {
+                if after_section_sign {
+                    after_section_sign = false;
+                } else if c == '§' {
+                    after_section_sign = true;
+                } else if c == '\n' {
+                    widest = widest.max(line);
+                    line = 0;
+                } else {
+                    line += metrics.char_width(c, bold);
+                }
+            }
+        }
+        widest.max(line)
+    }
+
+    /// Centers this component within `width`, measured with `metrics`, by surrounding it with
+    /// `fill` characters.
+    ///
+    /// The padding is unstyled, so e.g. underlined text doesn't underline the spaces around
+    /// it. When the space left over isn't an even number of fill characters, the extra one
+    /// goes on the right, biasing the content to the left. A component already as wide as
+    /// `width` is only wrapped, without padding.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let title = Component::text("Hi").centered(7, &FontMetrics::monospace(), '-');
+    /// assert_eq!(title.to_plain_text(), "--Hi---");
+    /// ```
+    #[must_use]
+    pub fn centered(&self, width: u32, metrics: &FontMetrics, fill: char) -> Component {
+        let fill_width = metrics.char_width(fill, false);
+        let remaining = width.saturating_sub(self.display_width(metrics));
+        let fills = remaining.checked_div(fill_width).unwrap_or(0);
+        let left = fills / 2;
+        let right = fills - left;
+
+        let padding = |count: u32| {
+            (count > 0)
+                .then(|| Component::String(std::iter::repeat_n(fill, count as usize).collect()))
+        };
+        let children = padding(left)
+            .into_iter()
+            .chain(std::iter::once(self.clone()))
+            .chain(padding(right))
+            .collect();
+        // An unstyled root keeps the padding from inheriting the content's style
+        Component::text("").set_children(children)
+    }
+
+    /// Shortens this component to at most `max_chars` characters of plain text, appending
+    /// `ellipsis` when anything was cut.
+    ///
+    /// Characters are counted as in [`to_plain_text`](Component::to_plain_text), one per
+    /// `char`, and the ellipsis doesn't count toward `max_chars`. The text that is kept keeps
+    /// its styling, while the ellipsis is unstyled. Components without text of their own,
+    /// such as translations, are kept whole as long as they come before the cut.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("Hello ").append(Component::text("world"));
+    /// assert_eq!(message.truncate(8, "...").to_plain_text(), "Hello wo...");
+    /// assert_eq!(message.truncate(11, "..."), message);
+    /// ```
+    #[must_use]
+    pub fn truncate(&self, max_chars: usize, ellipsis: &str) -> Component {
+        let mut remaining = max_chars;
+        let mut cut = false;
+        let kept = self
+            .truncated(&mut remaining, &mut cut)
+            .unwrap_or_else(|| Component::text(""));
+        if !cut {
+            return kept;
+        }
+        // An unstyled root keeps the ellipsis from inheriting the kept text's style
+        Component::text("").set_children(vec![kept, Component::String(ellipsis.to_owned())])
+    }
+
+    /// Keeps the first `remaining` characters of this component, or `None` when the cut
+    /// happened before it
+    fn truncated(&self, remaining: &mut usize, cut: &mut bool) -> Option<Component> {
+        if *cut {
+            return None;
+        }
+        match self {
+            Component::String(text) => take_chars(text, remaining, cut).map(Component::String),
+            Component::Object(obj) => {
+                let mut obj = obj.clone();
+                if let Some(text) = obj.text.take() {
+                    obj.text = Some(take_chars(&text, remaining, cut)?);
+                }
+                if let Some(children) = obj.extra.take() {
+                    let children: Vec<_> = children
+                        .iter()
+                        .map_while(|child| child.truncated(remaining, cut))
+                        .collect();
+                    obj.extra = (!children.is_empty()).then_some(children);
+                }
+                Some(Component::Object(obj))
+            }
+            Component::Array(components) => {
+                let components: Vec<_> = components
+                    .iter()
+                    .map_while(|component| component.truncated(remaining, cut))
+                    .collect();
+                (!components.is_empty()).then_some(Component::Array(components))
+            }
+        }
+    }
+
+    /// Word-wraps the resolved text of this component onto the four lines of a sign.
+    ///
+    /// Vanilla signs are 90px wide, so `max_width_px` is usually `90`. Text is broken at
+    /// spaces and at `\n`; a word wider than a whole line is broken between characters.
+    /// Styles, including inherited ones, are kept on every piece of text, and bold text is
+    /// measured one pixel wider per glyph.
+    ///
+    /// Lines beyond the fourth are dropped. Unused lines are empty text components.
+    pub fn to_sign_lines(&self, max_width_px: u32, font_metrics: &FontMetrics) -> [Component; 4] {
+        let mut lines = vec![Line::default()];
+        // Spaces are held back until the next word, so they never start or end a line
+        let mut pending_spaces: Vec<(char, &Style, u32)> = Vec::new();
+        let mut word: Vec<(char, &Style, u32)> = Vec::new();
+
+        let runs = self.styled_runs();
+        let chars = runs
+            .iter()
+            .flat_map(|(text, style)| text.chars().map(move |c| (c, style)))
+            .map(|(c, style)| {
+                let width = font_metrics.char_width(c, style.bold == Some(true));
+                (c, style, width)
+            })
+            .chain(std::iter::once(('\n', &EMPTY_STYLE, 0)));
+
+        for (c, style, width) in chars {
+            if c != ' ' && c != '\n' {
+                word.push((c, style, width));
+                continue;
+            }
+
+            place_word(&mut lines, &mut pending_spaces, &mut word, max_width_px);
+            if c == '\n' {
+                pending_spaces.clear();
+                lines.push(Line::default());
+            } else {
+                pending_spaces.push((c, style, width));
+            }
+        }
+
+        let mut lines = lines.into_iter().map(Line::into_component);
+        std::array::from_fn(|_| lines.next().unwrap_or_else(|| Component::text("")))
+    }
+}
+
+/// Takes up to `remaining` characters of `text`, setting `cut` when some are left over.
+/// Returns `None` when nothing could be kept from a non-empty `text`.
+fn take_chars(text: &str, remaining: &mut usize, cut: &mut bool) -> Option<String> {
+    match text.char_indices().nth(*remaining) {
+        None => {
+            *remaining -= text.chars().count();
+            Some(text.to_owned())
+        }
+        Some((0, _)) => {
+            *cut = true;
+            None
+        }
+        Some((end, _)) => {
+            *remaining = 0;
+            *cut = true;
+            Some(text[..end].to_owned())
+        }
+    }
+}
+
+/// Places the buffered word (preceded by any pending spaces) on the current line, starting a
+/// new line when it doesn't fit
+fn place_word(
+    lines: &mut Vec<Line>,
+    pending_spaces: &mut Vec<(char, &Style, u32)>,
+    word: &mut Vec<(char, &Style, u32)>,
+    max_width: u32,
+) {
+    if word.is_empty() {
+        return;
+    }
+    let word_width: u32 = word.iter().map(|(_, _, width)| width).sum();
+    let spaces_width: u32 = pending_spaces.iter().map(|(_, _, width)| width).sum();
+
+    let Some(line) = lines.last_mut() else {
+        return;
+    };
+    if line.pieces.is_empty() || line.width + spaces_width + word_width <= max_width {
+        if !line.pieces.is_empty() {
+            for (c, style, width) in pending_spaces.iter() {
+                line.push(*c, style, *width);
+            }
+        }
+    } else {
+        lines.push(Line::default());
+    }
+    pending_spaces.clear();
+
+    for (c, style, width) in word.drain(..) {
+        let Some(line) = lines.last_mut() else {
+            return;
+        };
+        if !line.pieces.is_empty() && line.width + width > max_width {
+            lines.push(Line::default());
+        }
+        if let Some(line) = lines.last_mut() {
+            line.push(c, style, width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_font_provider_widths() {
+        let font = r#"{
+            "providers": [
+                {"type": "space", "advances": {" ": 4, "\u200c": 0, "\uF801": -1.6}},
+                {"type": "ttf", "file": "custom:font.ttf", "shift": [0, 0], "size": 11},
+                {
+                    "type": "bitmap",
+                    "file": "custom:font/icons.png",
+                    "ascent": 7,
+                    "chars": ["\uE000\uE001", "\u0000 "]
+                },
+                {"type": "bitmap", "file": "custom:font/big.png", "height": 16, "ascent": 12, "chars": ["\uE000\uE002"]}
+            ]
+        }"#;
+        let metrics = FontMetrics::from_font_provider(font).unwrap();
+        assert_eq!(metrics.char_width(' ', false), 4);
+        assert_eq!(metrics.char_width('\u{200C}', false), 0);
+        assert_eq!(metrics.char_width('\u{F801}', false), 0);
+        // The first provider defining a glyph wins
+        assert_eq!(metrics.char_width('\u{E000}', false), 9);
+        assert_eq!(metrics.char_width('\u{E001}', true), 10);
+        assert_eq!(metrics.char_width('\u{E002}', false), 17);
+        // Empty cells and undefined characters fall back to the default width
+        assert_eq!(metrics.char_width('\0', false), 6);
+        assert_eq!(metrics.char_width('a', false), 6);
+
+        assert!(matches!(
+            FontMetrics::from_font_provider(
+                r#"{"providers": [{"type": "bitmap", "file": "f.png", "height": 8, "ascent": 9, "chars": ["a"]}]}"#
+            ),
+            Err(FontProviderError::AscentAboveHeight {
+                ascent: 9,
+                height: 8
+            })
+        ));
+        assert!(matches!(
+            FontMetrics::from_font_provider(
+                r#"{"providers": [{"type": "bitmap", "file": "f.png", "ascent": 7, "chars": ["ab", "c"]}]}"#
+            ),
+            Err(FontProviderError::UnevenRows)
+        ));
+        assert!(matches!(
+            FontMetrics::from_font_provider("{}"),
+            Err(FontProviderError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_lines_wrap_at_spaces() {
+        let text = Component::text("The quick brown fox jumps over the lazy dog");
+        let lines = text.to_sign_lines(90, &FontMetrics::vanilla_default());
+        let lines: Vec<_> = lines.iter().map(|l| l.to_plain_text()).collect();
+        assert_eq!(
+            lines,
+            ["The quick brown", "fox jumps over", "the lazy dog", ""]
+        );
+    }
+
+    #[test]
+    fn test_sign_lines_keep_styles_and_truncate() {
+        let metrics = FontMetrics::new(6);
+        // Each line fits three 6px glyphs
+        let text = Component::text("aaa ")
+            .color(Some(Color::Named(NamedColor::Red)))
+            .append(Component::text("bbbbbbccc dd\neee fff"));
+        let lines = text.to_sign_lines(18, &metrics);
+        let plain: Vec<_> = lines.iter().map(|l| l.to_plain_text()).collect();
+        assert_eq!(plain, ["aaa", "bbb", "bbb", "ccc"]);
+        // The red color is inherited by the appended child, so it carries over to every line
+        for line in &lines {
+            assert_eq!(
+                line,
+                &Component::text(line.to_plain_text()).color(Some(Color::Named(NamedColor::Red)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_sign_lines_bold_is_wider() {
+        let metrics = FontMetrics::new(6);
+        let plain = Component::text("aaa aaa").to_sign_lines(42, &metrics);
+        assert_eq!(plain[0].to_plain_text(), "aaa aaa");
+
+        let bold = Component::text("aaa aaa")
+            .decoration(TextDecoration::Bold, Some(true))
+            .to_sign_lines(42, &metrics);
+        assert_eq!(bold[0].to_plain_text(), "aaa");
+        assert_eq!(bold[1].to_plain_text(), "aaa");
+    }
+
+    #[test]
+    fn test_display_width() {
+        let mono = FontMetrics::monospace();
+        let vanilla = FontMetrics::vanilla_default();
+
+        let ascii = Component::text("Hello!");
+        assert_eq!(ascii.display_width(&mono), 6);
+        assert_eq!(ascii.display_width(&vanilla), 6 + 6 + 3 + 3 + 6 + 2);
+
+        // Chinese, Japanese, Korean and fullwidth forms are all double width
+        let cjk = Component::from("中文かなカナ한글ＡＢ");
+        assert_eq!(cjk.display_width(&mono), 20);
+        assert_eq!(cjk.display_width(&vanilla), 10 * 9);
+        // Non-wide characters outside ASCII are single width
+        assert_eq!(Component::from("é").display_width(&mono), 1);
+
+        // The widest line counts, formatting codes are skipped, bold adds a pixel per glyph
+        let mixed = Component::text("§cab\nabc")
+            .append(Component::text("de").decoration(TextDecoration::Bold, Some(true)));
+        assert_eq!(mixed.display_width(&mono), 5);
+        assert_eq!(mixed.display_width(&FontMetrics::new(6)), 3 * 6 + 2 * 7);
+        // A trailing `§` only skips itself
+        let trailing = Component::text("a§").append(Component::text("bc"));
+        assert_eq!(trailing.display_width(&mono), 3);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let message = Component::text("Hello, ")
+            .color(red.clone())
+            .append(Component::text("wörld").decoration(TextDecoration::Bold, Some(true)))
+            .append("!");
+
+        // Cut inside the root's own text: the children are dropped
+        assert_eq!(
+            message.truncate(4, "…"),
+            Component::text("")
+                .set_children(vec![Component::text("Hell").color(red.clone()), "…".into()])
+        );
+
+        // Cut inside a child, on a char boundary past the multibyte 'ö'
+        assert_eq!(
+            message.truncate(9, "..."),
+            Component::text("").set_children(vec![
+                Component::text("Hello, ")
+                    .color(red.clone())
+                    .append(Component::text("wö").decoration(TextDecoration::Bold, Some(true))),
+                "...".into(),
+            ])
+        );
+
+        // Cut exactly between two children
+        let at_boundary = message.truncate(12, "...");
+        assert_eq!(at_boundary.to_plain_text(), "Hello, wörld...");
+
+        // Short enough already: unchanged, without an ellipsis
+        assert_eq!(message.truncate(13, "..."), message);
+        assert_eq!(message.truncate(100, "..."), message);
+
+        // Nothing kept at all
+        assert_eq!(message.truncate(0, "...").to_plain_text(), "...");
+    }
+
+    #[test]
+    fn test_centered() {
+        let mono = FontMetrics::monospace();
+        let hi = Component::text("Hi").decoration(TextDecoration::Underlined, Some(true));
+
+        let centered = hi.centered(10, &mono, ' ');
+        assert_eq!(centered.to_plain_text(), "    Hi    ");
+        assert_eq!(
+            centered,
+            Component::text("").set_children(vec![
+                Component::from("    "),
+                hi.clone(),
+                Component::from("    "),
+            ])
+        );
+
+        // Odd remainders put the extra fill on the right
+        assert_eq!(hi.centered(11, &mono, ' ').to_plain_text(), "    Hi     ");
+        // Too narrow: nothing to pad
+        assert_eq!(hi.centered(1, &mono, ' ').to_plain_text(), "Hi");
+
+        // Pixel widths: "Hi" is 8px wide, leaving room for six 4px spaces plus 2px
+        let vanilla = FontMetrics::vanilla_default();
+        assert_eq!(hi.centered(34, &vanilla, ' ').to_plain_text(), "   Hi   ");
+    }
+}
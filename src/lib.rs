@@ -93,21 +93,53 @@
 #![forbid(missing_copy_implementations, missing_debug_implementations)]
 #![forbid(unsafe_code)]
 
+pub mod binary;
 mod colors;
+mod compact;
+mod debug_tree;
+mod decoration;
+mod layout;
+pub mod limits;
 mod macros;
 pub mod parsing;
+mod sanitize;
+pub mod snbt;
+mod stats;
+mod translation;
+mod traversal;
+pub mod version;
 
 #[cfg(feature = "minimessage")]
 pub mod minimessage;
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign};
 use std::{collections::HashMap, fmt, str::FromStr};
 
+pub use decoration::DecorationSet;
+pub use layout::{FontMetrics, FontProviderError};
+#[doc(hidden)]
+pub use macros::__private;
+pub use sanitize::SanitizePolicy;
+pub use stats::StyleStats;
+pub use traversal::{Nodes, StyledSpan};
+pub use version::McVersion;
+
 /// Represents a Minecraft text component. Allows de/serialization using Serde with JSON.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// Like Minecraft, deserialization also accepts a bare number or boolean (e.g. `5` or `true`)
+/// as shorthand text. It becomes a [`Component::String`] holding the value's JSON text, so it
+/// is serialized back as a string (`"5"`), which the game displays the same way.
+///
+/// Minecraft rejects an empty array, so an empty [`Component::Array`] is serialized as the
+/// empty string `""`, which renders the same, and `[]` is deserialized as an empty
+/// [`Component::String`]. A single-element array is valid and kept as is;
+/// [`Component::compact`] replaces it by its element.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "RawComponent")]
 pub enum Component {
     /// Simple string component (shorthand for `{text: "value"}`)
     String(String),
@@ -117,11 +149,54 @@ pub enum Component {
     Object(Box<ComponentObject>),
 }
 
+/// Every JSON shape a component can be written in
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawComponent {
+    String(String),
+    Bool(bool),
+    Number(serde_json::Number),
+    Array(Vec<Component>),
+    Object(Box<ComponentObject>),
+}
+
+impl From<RawComponent> for Component {
+    fn from(raw: RawComponent) -> Self {
+        match raw {
+            RawComponent::String(text) => Component::String(text),
+            RawComponent::Bool(value) => Component::String(value.to_string()),
+            RawComponent::Number(value) => Component::String(value.to_string()),
+            RawComponent::Array(children) if children.is_empty() => {
+                Component::String(String::new())
+            }
+            RawComponent::Array(children) => Component::Array(children),
+            RawComponent::Object(obj) => Component::Object(obj),
+        }
+    }
+}
+
+impl Serialize for Component {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Component::String(text) => serializer.serialize_str(text),
+            // `[]` isn't a valid component, but `""` renders the same
+            Component::Array(children) if children.is_empty() => serializer.serialize_str(""),
+            Component::Array(children) => children.serialize(serializer),
+            Component::Object(obj) => obj.serialize(serializer),
+        }
+    }
+}
+
 /// Content type of a component object
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ContentType {
-    /// Plain text content
+    /// Plain text content. `"literal"`, used by some data-pack and mod tooling, is accepted
+    /// as an alias and serialized back as `"text"`.
+    #[serde(alias = "literal")]
     Text,
     /// Localized translation text
     Translatable,
@@ -135,6 +210,20 @@ pub enum ContentType {
     Nbt,
 }
 
+impl ContentType {
+    /// The object field holding this type's content
+    fn field_name(self) -> &'static str {
+        match self {
+            ContentType::Text => "text",
+            ContentType::Translatable => "translate",
+            ContentType::Score => "score",
+            ContentType::Selector => "selector",
+            ContentType::Keybind => "keybind",
+            ContentType::Nbt => "nbt",
+        }
+    }
+}
+
 /// Named text colors from Minecraft
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -208,6 +297,101 @@ impl Color {
                 .map(|(_, n)| *n),
         }
     }
+
+    /// Gets the named color closest to this one in RGB, for clients that can't display hex
+    /// colors. Returns `None` for a hex string that isn't valid `#RRGGBB`.
+    ///
+    /// ```
+    /// use kyori_component_json::{Color, NamedColor};
+    ///
+    /// assert_eq!(Color::Hex("#FF4040".into()).to_nearest_named(), Some(NamedColor::Red));
+    /// ```
+    pub fn to_nearest_named(&self) -> Option<NamedColor> {
+        if let Color::Named(named) = self {
+            return Some(*named);
+        }
+        let rgb = self.rgb()?;
+        colors::HEX_CODE_TO_NAMED_COLOR
+            .iter()
+            .filter_map(|(hex, named)| Some((parse_hex_color(hex)?, *named)))
+            .min_by_key(|(named_rgb, _)| {
+                named_rgb
+                    .iter()
+                    .zip(rgb)
+                    .map(|(a, b)| (i32::from(*a) - i32::from(b)).pow(2))
+                    .sum::<i32>()
+            })
+            .map(|(_, named)| named)
+    }
+
+    /// Parses a `#RRGGBB` hex color (digits in either case) into its red, green and blue
+    /// channels.
+    ///
+    /// This is the format Minecraft accepts for text colors; shorthand `#RGB` and colors with
+    /// an alpha channel are rejected. [`Color`]'s [`FromStr`] uses it for hex colors.
+    ///
+    /// ```
+    /// use kyori_component_json::Color;
+    ///
+    /// assert_eq!(Color::parse_hex("#FFAA00"), Some([0xFF, 0xAA, 0x00]));
+    /// assert_eq!(Color::parse_hex("#ffaa00"), Some([0xFF, 0xAA, 0x00]));
+    /// assert_eq!(Color::parse_hex("FFAA00"), None);
+    /// assert_eq!(Color::parse_hex("#FA0"), None);
+    /// ```
+    pub fn parse_hex(s: &str) -> Option<[u8; 3]> {
+        parse_hex_color(s)
+    }
+
+    /// Gets the red, green and blue channels of this color.
+    ///
+    /// Named colors use their vanilla values (e.g. `red` is `#FF5555`). Returns `None` for a
+    /// hex string that isn't valid `#RRGGBB`.
+    pub fn rgb(&self) -> Option<[u8; 3]> {
+        match self {
+            Color::Named(named) => colors::HEX_CODE_TO_NAMED_COLOR
+                .iter()
+                .find(|(_, n)| n == named)
+                .and_then(|(hex, _)| parse_hex_color(hex)),
+            Color::Hex(hex) => parse_hex_color(hex),
+        }
+    }
+
+    /// Linearly interpolates between this color and `other` in RGB, returning a hex color.
+    ///
+    /// `t` is clamped to `[0, 1]`: `0` gives this color and `1` gives `other`. If either color
+    /// has no RGB value (see [`Self::rgb`]), this color is returned unchanged.
+    ///
+    /// ```
+    /// use kyori_component_json::Color;
+    ///
+    /// let red = Color::from((255, 0, 0));
+    /// let blue = Color::from((0, 0, 255));
+    /// assert_eq!(red.lerp(&blue, 0.5), Color::Hex("#800080".into()));
+    /// ```
+    #[must_use]
+    pub fn lerp(&self, other: &Color, t: f32) -> Color {
+        let (Some(from), Some(to)) = (self.rgb(), other.rgb()) else {
+            return self.clone();
+        };
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let channel = |i: usize| {
+            let (a, b) = (f32::from(from[i]), f32::from(to[i]));
+            // Both ends are within `0..=255` and `t` is clamped, so the cast can't truncate
+            (a + (b - a) * t).round() as u8
+        };
+        Color::from([channel(0), channel(1), channel(2)])
+    }
+
+    /// Composites this color at opacity `alpha` over the opaque `background`.
+    ///
+    /// `alpha` is clamped to `[0, 1]`: `1` gives this color and `0` gives `background`.
+    #[must_use]
+    pub fn blend(&self, background: &Color, alpha: f32) -> Color {
+        match (self.rgb(), background.rgb()) {
+            (Some(_), Some(_)) => background.lerp(self, alpha),
+            _ => self.clone(),
+        }
+    }
 }
 
 impl fmt::Display for Color {
@@ -243,12 +427,31 @@ impl Serialize for Color {
     }
 }
 
+/// Every JSON shape a color can be written in
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawColor {
+    String(String),
+    /// `0xRRGGBB` packed into an integer, as some tools emit it
+    Int(u32),
+}
+
+/// Accepts a color name, a hex string or an integer `0xRRGGBB` (e.g. `16755200` for
+/// `#FFAA00`). Integers are always read as [`Color::Hex`] and serialized back as hex strings.
 impl<'de> Deserialize<'de> for Color {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
+        let s = match RawColor::deserialize(deserializer)? {
+            RawColor::String(s) => s,
+            RawColor::Int(rgb) if rgb <= 0xFF_FFFF => return Ok(Color::Hex(format!("#{rgb:06X}"))),
+            RawColor::Int(rgb) => {
+                return Err(serde::de::Error::custom(format!(
+                    "integer color {rgb} is out of range for 0xRRGGBB"
+                )));
+            }
+        };
         if let Ok(named) = serde_json::from_str::<NamedColor>(&format!("\"{s}\"")) {
             Ok(Color::Named(named))
         } else {
@@ -258,15 +461,137 @@ impl<'de> Deserialize<'de> for Color {
 }
 
 /// Shadow color representation (integer or float array)
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// Float channels are serialized clamped to `[0, 1]` and rounded to
+/// [`ShadowColor::FLOAT_DECIMALS`] decimal places, so `1.0 / 3.0` is written as `0.33333`
+/// rather than `0.33333334`. Deserializing float channels outside `[0, 1]` fails.
+///
+/// Float channels are compared by value, except that NaN equals NaN, which keeps equality
+/// reflexive so `ShadowColor` can implement [`Eq`] and [`Hash`](std::hash::Hash). `0.0` and
+/// `-0.0` are equal and hash the same.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "RawShadowColor")]
 pub enum ShadowColor {
-    /// RGBA packed as 32-bit integer (0xRRGGBBAA)
+    /// ARGB packed as a 32-bit integer (0xAARRGGBB), the way Minecraft stores it
     Int(i32),
     /// RGBA as [0.0-1.0] float values
     Floats([f32; 4]),
 }
 
+impl ShadowColor {
+    /// Number of decimal places float channels are rounded to when serialized
+    pub const FLOAT_DECIMALS: i32 = 5;
+
+    /// Packs red, green, blue and alpha channels into [`ShadowColor::Int`]
+    ///
+    /// ```
+    /// use kyori_component_json::ShadowColor;
+    ///
+    /// assert_eq!(ShadowColor::from_rgba([0, 0, 0, 255]), ShadowColor::Int(-16777216));
+    /// ```
+    pub const fn from_rgba([r, g, b, a]: [u8; 4]) -> Self {
+        ShadowColor::Int(i32::from_be_bytes([a, r, g, b]))
+    }
+
+    /// Gets the red, green, blue and alpha channels, rounding float channels to the
+    /// nearest byte
+    pub fn to_rgba(&self) -> [u8; 4] {
+        match self {
+            ShadowColor::Int(value) => {
+                let [a, r, g, b] = value.to_be_bytes();
+                [r, g, b, a]
+            }
+            ShadowColor::Floats(channels) => channels.map(|c| {
+                let c = if c.is_nan() { 0.0 } else { c.clamp(0.0, 1.0) };
+                // In range after clamping, so the cast can't truncate
+                (c * 255.0).round() as u8
+            }),
+        }
+    }
+}
+
+/// Bits of `channel` with every NaN and both zeros mapped to a single representation
+fn canonical_channel_bits(channel: f32) -> u32 {
+    if channel.is_nan() {
+        f32::NAN.to_bits()
+    } else if channel == 0.0 {
+        0
+    } else {
+        channel.to_bits()
+    }
+}
+
+impl PartialEq for ShadowColor {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ShadowColor::Int(a), ShadowColor::Int(b)) => a == b,
+            (ShadowColor::Floats(a), ShadowColor::Floats(b)) => {
+                a.map(canonical_channel_bits) == b.map(canonical_channel_bits)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ShadowColor {}
+
+impl std::hash::Hash for ShadowColor {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            ShadowColor::Int(value) => value.hash(state),
+            ShadowColor::Floats(channels) => channels.map(canonical_channel_bits).hash(state),
+        }
+    }
+}
+
+/// Unvalidated shadow color, as found in JSON
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawShadowColor {
+    Int(i32),
+    Floats([f32; 4]),
+}
+
+impl TryFrom<RawShadowColor> for ShadowColor {
+    type Error = String;
+
+    fn try_from(raw: RawShadowColor) -> Result<Self, Self::Error> {
+        match raw {
+            RawShadowColor::Int(value) => Ok(ShadowColor::Int(value)),
+            RawShadowColor::Floats(channels) => {
+                if let Some(channel) = channels.iter().find(|c| !(0.0..=1.0).contains(*c)) {
+                    return Err(format!(
+                        "shadow_color channel {channel} is outside the range [0, 1]"
+                    ));
+                }
+                Ok(ShadowColor::Floats(channels))
+            }
+        }
+    }
+}
+
+impl Serialize for ShadowColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            ShadowColor::Int(value) => value.serialize(serializer),
+            ShadowColor::Floats(channels) => {
+                let scale = 10f32.powi(ShadowColor::FLOAT_DECIMALS);
+                channels
+                    .map(|c| {
+                        // NaN would otherwise be written as `null`
+                        let c = if c.is_nan() { 0.0 } else { c.clamp(0.0, 1.0) };
+                        (c * scale).round() / scale
+                    })
+                    .serialize(serializer)
+            }
+        }
+    }
+}
+
 /// Actions triggered when clicking text
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "action")]
@@ -284,6 +609,65 @@ pub enum ClickEvent {
     ChangePage { page: i32 },
     /// Copy text to clipboard
     CopyToClipboard { value: String },
+    /// Open a dialog (1.21.6+), either a dialog ID such as `"minecraft:server_links"` or an
+    /// inline dialog definition
+    ShowDialog { dialog: Value },
+    /// Send a custom payload to the server (1.21.6+), for server-side handling
+    Custom {
+        id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<Value>,
+    },
+    /// An action this crate doesn't know, kept as the raw event object, `action` included, so
+    /// it survives a round trip. A known action missing one of its fields also ends up here.
+    #[serde(untagged)]
+    Other(Map<String, Value>),
+}
+
+impl ClickEvent {
+    /// The `action` this event is serialized with, e.g. `"run_command"`
+    pub fn action(&self) -> &str {
+        match self {
+            ClickEvent::OpenUrl { .. } => "open_url",
+            ClickEvent::OpenFile { .. } => "open_file",
+            ClickEvent::RunCommand { .. } => "run_command",
+            ClickEvent::SuggestCommand { .. } => "suggest_command",
+            ClickEvent::ChangePage { .. } => "change_page",
+            ClickEvent::CopyToClipboard { .. } => "copy_to_clipboard",
+            ClickEvent::ShowDialog { .. } => "show_dialog",
+            ClickEvent::Custom { .. } => "custom",
+            ClickEvent::Other(event) => event
+                .get("action")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The command run or suggested by a `run_command` or `suggest_command` event, `None` for
+    /// other actions
+    ///
+    /// ```
+    /// use kyori_component_json::ClickEvent;
+    ///
+    /// let event = ClickEvent::RunCommand { command: "/spawn".into() };
+    /// assert_eq!(event.command(), Some("/spawn"));
+    /// ```
+    pub fn command(&self) -> Option<&str> {
+        match self {
+            ClickEvent::RunCommand { command } | ClickEvent::SuggestCommand { command } => {
+                Some(command)
+            }
+            _ => None,
+        }
+    }
+
+    /// The URL opened by an `open_url` event, `None` for other actions
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            ClickEvent::OpenUrl { url } => Some(url),
+            _ => None,
+        }
+    }
 }
 
 /// UUID representation for entity hover events
@@ -328,6 +712,17 @@ pub enum HoverEvent {
     },
 }
 
+impl HoverEvent {
+    /// The `action` this event is serialized with, e.g. `"show_text"`
+    pub fn action(&self) -> &'static str {
+        match self {
+            HoverEvent::ShowText { .. } => "show_text",
+            HoverEvent::ShowItem { .. } => "show_item",
+            HoverEvent::ShowEntity { .. } => "show_entity",
+        }
+    }
+}
+
 /// Scoreboard value content
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScoreContent {
@@ -335,6 +730,27 @@ pub struct ScoreContent {
     pub name: String,
     /// Objective name
     pub objective: String,
+    /// Fixed value displayed instead of looking up the scoreboard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl ScoreContent {
+    /// Creates score content for `name`'s score in `objective`
+    pub fn new(name: impl Into<String>, objective: impl Into<String>) -> Self {
+        ScoreContent {
+            name: name.into(),
+            objective: objective.into(),
+            value: None,
+        }
+    }
+
+    /// Overrides the displayed value instead of reading the live scoreboard
+    #[must_use]
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
 }
 
 /// Source for NBT data
@@ -349,6 +765,41 @@ pub enum NbtSource {
     Storage,
 }
 
+/// Every JSON shape a decoration flag is accepted in
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawBool {
+    Bool(bool),
+    /// `"true"` or `"false"`, as some data pipelines quote every value
+    String(String),
+    /// `1` or `0`
+    Int(i64),
+}
+
+/// Reads a decoration flag from a boolean, the strings `"true"`/`"false"` or the integers
+/// `1`/`0`. Flags are always serialized back as booleans.
+fn deserialize_lenient_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(raw) = Option::<RawBool>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    match raw {
+        RawBool::Bool(value) => Ok(Some(value)),
+        RawBool::String(s) if s == "true" => Ok(Some(true)),
+        RawBool::String(s) if s == "false" => Ok(Some(false)),
+        RawBool::Int(1) => Ok(Some(true)),
+        RawBool::Int(0) => Ok(Some(false)),
+        RawBool::String(s) => Err(serde::de::Error::custom(format!(
+            "invalid boolean \"{s}\", expected true or false"
+        ))),
+        RawBool::Int(n) => Err(serde::de::Error::custom(format!(
+            "invalid boolean {n}, expected 0 or 1"
+        ))),
+    }
+}
+
 /// Core component structure containing all properties
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -426,23 +877,43 @@ pub struct ComponentObject {
     pub font: Option<String>,
 
     /// Bold formatting
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub bold: Option<bool>,
 
     /// Italic formatting
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub italic: Option<bool>,
 
     /// Underline formatting
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub underlined: Option<bool>,
 
     /// Strikethrough formatting
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub strikethrough: Option<bool>,
 
     /// Obfuscated text
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub obfuscated: Option<bool>,
 
     /// Text shadow color
@@ -489,6 +960,49 @@ pub struct Style {
     pub hover_event: Option<HoverEvent>,
 }
 
+/// A style with no properties set, for places that need a `&'static Style`
+pub(crate) static EMPTY_STYLE: Style = Style {
+    color: None,
+    font: None,
+    bold: None,
+    italic: None,
+    underlined: None,
+    strikethrough: None,
+    obfuscated: None,
+    shadow_color: None,
+    insertion: None,
+    click_event: None,
+    hover_event: None,
+};
+
+impl Style {
+    /// Fills every property that is unset on this style with the one from `parent`,
+    /// the way Minecraft resolves inherited styles.
+    pub(crate) fn inherit(&mut self, parent: &Style) {
+        if self.color.is_none() {
+            self.color.clone_from(&parent.color);
+        }
+        if self.font.is_none() {
+            self.font.clone_from(&parent.font);
+        }
+        self.bold = self.bold.or(parent.bold);
+        self.italic = self.italic.or(parent.italic);
+        self.underlined = self.underlined.or(parent.underlined);
+        self.strikethrough = self.strikethrough.or(parent.strikethrough);
+        self.obfuscated = self.obfuscated.or(parent.obfuscated);
+        self.shadow_color = self.shadow_color.or(parent.shadow_color);
+        if self.insertion.is_none() {
+            self.insertion.clone_from(&parent.insertion);
+        }
+        if self.click_event.is_none() {
+            self.click_event.clone_from(&parent.click_event);
+        }
+        if self.hover_event.is_none() {
+            self.hover_event.clone_from(&parent.hover_event);
+        }
+    }
+}
+
 /// Text decoration styles
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TextDecoration {
@@ -553,21 +1067,244 @@ pub enum StyleMerge {
 
 impl Component {
     /// Creates a plain text component
+    ///
+    /// The `type` field is left unset, matching what vanilla emits for text components.
+    ///
+    /// The text is always copied; use [`Component::text_owned`] to move a `String` you no
+    /// longer need instead.
     #[must_use]
     pub fn text(text: impl AsRef<str>) -> Self {
+        Self::text_owned(text.as_ref().to_string())
+    }
+
+    /// Creates a plain text component from an owned string, moving it in without copying.
+    ///
+    /// Builds the same component as [`Component::text`], but saves an allocation when the
+    /// string was built just for the component, e.g. with `format!`.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let score = 42;
+    /// let text = Component::text_owned(format!("Score: {score}"));
+    /// assert_eq!(text, Component::text("Score: 42"));
+    /// ```
+    #[must_use]
+    pub fn text_owned(text: String) -> Self {
+        Component::Object(Box::new(ComponentObject {
+            text: Some(text),
+            ..Default::default()
+        }))
+    }
+
+    /// Creates a text component with no text, `{"text":""}`.
+    ///
+    /// Useful as a style-only parent for children. [`Component::default`] is the shorter
+    /// `""` form, which can't carry any style.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// assert_eq!(serde_json::to_string(&Component::empty()).unwrap(), r#"{"text":""}"#);
+    /// ```
+    #[must_use]
+    pub fn empty() -> Self {
+        Component::text("")
+    }
+
+    /// Creates a text component holding a line break, `{"text":"\n"}`
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let lines = Component::Array(vec![
+    ///     Component::text("first"),
+    ///     Component::newline(),
+    ///     Component::text("second"),
+    /// ]);
+    /// assert_eq!(lines.to_plain_text(), "first\nsecond");
+    /// ```
+    #[must_use]
+    pub fn newline() -> Self {
+        Component::text("\n")
+    }
+
+    /// Creates a text component holding a single space, `{"text":" "}`
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// assert_eq!(Component::space(), Component::text(" "));
+    /// ```
+    #[must_use]
+    pub fn space() -> Self {
+        Component::text(" ")
+    }
+
+    /// Creates a translatable component, `{"translate":"<key>"}`, which the client displays
+    /// in the player's language.
+    ///
+    /// Use [`Component::with_arg`] to fill in the `%s` placeholders of the translation.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let death = Component::translatable("death.attack.generic").with_text_arg("Steve");
+    /// assert_eq!(
+    ///     serde_json::to_string(&death).unwrap(),
+    ///     r#"{"translate":"death.attack.generic","with":["Steve"]}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn translatable(key: impl Into<String>) -> Self {
+        Component::Object(Box::new(ComponentObject {
+            translate: Some(key.into()),
+            ..Default::default()
+        }))
+    }
+
+    /// Creates a translatable component with a `fallback`, which clients without a
+    /// translation for `key` display instead of the raw key.
+    ///
+    /// The fallback is a format string like the translation itself, so `%s` placeholders
+    /// are filled from the same arguments. Fallbacks need Minecraft 1.19.4 or newer.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let welcome = Component::translatable_with_fallback("myplugin.welcome", "Welcome, %s!")
+    ///     .with_text_arg("Steve");
+    /// assert_eq!(
+    ///     serde_json::to_string(&welcome).unwrap(),
+    ///     r#"{"translate":"myplugin.welcome","fallback":"Welcome, %s!","with":["Steve"]}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn translatable_with_fallback(key: impl Into<String>, fallback: impl Into<String>) -> Self {
+        Component::Object(Box::new(ComponentObject {
+            translate: Some(key.into()),
+            fallback: Some(fallback.into()),
+            ..Default::default()
+        }))
+    }
+
+    /// Creates a keybind component, `{"keybind":"<key>"}`, which the client displays as the
+    /// key the player bound to it, e.g. `key.jump`
+    #[must_use]
+    pub fn keybind(key: impl Into<String>) -> Self {
+        Component::Object(Box::new(ComponentObject {
+            keybind: Some(key.into()),
+            ..Default::default()
+        }))
+    }
+
+    /// Creates a score component, which the server replaces with `name`'s score in the
+    /// `objective` scoreboard objective. `name` can be a player name or a selector.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let kills = Component::score("@s", "kills");
+    /// assert_eq!(
+    ///     serde_json::to_string(&kills).unwrap(),
+    ///     r#"{"score":{"name":"@s","objective":"kills"}}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn score(name: impl Into<String>, objective: impl Into<String>) -> Self {
+        Component::Object(Box::new(ComponentObject {
+            score: Some(ScoreContent {
+                name: name.into(),
+                objective: objective.into(),
+                value: None,
+            }),
+            ..Default::default()
+        }))
+    }
+
+    /// Creates a selector component, `{"selector":"<selector>"}`, which the server replaces
+    /// with the names of the entities `selector` matches, e.g. `@a`
+    #[must_use]
+    pub fn selector(selector: impl Into<String>) -> Self {
         Component::Object(Box::new(ComponentObject {
-            text: Some(text.as_ref().to_string()),
+            selector: Some(selector.into()),
             ..Default::default()
         }))
     }
 
+    /// Creates an NBT component, which the server replaces with the NBT data at `path`.
+    ///
+    /// `target` says where to read it from, depending on `source`: block coordinates such as
+    /// `"~ ~-1 ~"`, an entity selector, or a storage ID.
+    ///
+    /// ```
+    /// use kyori_component_json::{Component, NbtSource};
+    ///
+    /// let health = Component::nbt("Health", NbtSource::Entity, "@p");
+    /// assert_eq!(
+    ///     serde_json::to_string(&health).unwrap(),
+    ///     r#"{"nbt":"Health","source":"entity","entity":"@p"}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn nbt(path: impl Into<String>, source: NbtSource, target: impl Into<String>) -> Self {
+        let target = Some(target.into());
+        let mut obj = ComponentObject {
+            nbt: Some(path.into()),
+            source: Some(source),
+            ..Default::default()
+        };
+        match source {
+            NbtSource::Block => obj.block = target,
+            NbtSource::Entity => obj.entity = target,
+            NbtSource::Storage => obj.storage = target,
+        }
+        Component::Object(Box::new(obj))
+    }
+
+    /// Builds a [`Component::Array`] with one text element per section, each carrying its
+    /// own style.
+    ///
+    /// The array starts with an empty string: Minecraft treats the first element of an array
+    /// as the parent of the rest, so this keeps each section's style from leaking into the
+    /// following ones. Adjacent sections with identical styles are not merged; use
+    /// [`Component::compact`] for that.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let red = Style {
+    ///     color: Some(Color::Named(NamedColor::Red)),
+    ///     ..Default::default()
+    /// };
+    /// let message = Component::from_sections([
+    ///     ("Error: ".to_string(), red),
+    ///     ("oops".to_string(), Style::default()),
+    /// ]);
+    /// assert_eq!(message.to_plain_text(), "Error: oops");
+    /// ```
+    pub fn from_sections(sections: impl IntoIterator<Item = (String, Style)>) -> Component {
+        let sections = sections
+            .into_iter()
+            .map(|(text, style)| Component::text(text).apply_fallback_style(&style));
+        Component::Array(
+            std::iter::once(Component::default())
+                .chain(sections)
+                .collect(),
+        )
+    }
+
     /// Appends a child component
+    ///
+    /// The new child inherits the root's style in every case: a string becomes a text object
+    /// with the child in its `extra`, an object gets the child added to its `extra`, and an
+    /// array gets the child pushed as a new element, which Minecraft styles like the array's
+    /// first element. Array roots only stop being arrays once styled, see [`Self::color`].
     #[must_use]
     pub fn append<C: Into<Component>>(self, component: C) -> Self {
         let component = component.into();
         match self {
             Component::String(s) => Component::Object(Box::new(ComponentObject {
-                content_type: Some(ContentType::Text),
                 text: Some(s),
                 extra: Some(vec![component]),
                 ..Default::default()
@@ -590,22 +1327,124 @@ impl Component {
     /// Appends a newline character
     #[must_use]
     pub fn append_newline(self) -> Self {
-        self.append(Component::text("\n"))
+        self.append(Component::newline())
     }
 
     /// Appends a space character
     #[must_use]
     pub fn append_space(self) -> Self {
-        self.append(Component::text(" "))
+        self.append(Component::space())
     }
 
-    /// Returns the "plain text" representation of this component as a [`Cow<str>`].
+    /// Puts a newline before this component, e.g. to start a message on its own line.
     ///
-    /// This is the closest equivalent to [Kyori's plain text serializer](https://javadoc.io/doc/net.kyori/adventure-text-serializer-plain/latest/net/kyori/adventure/text/serializer/plain/PlainTextComponentSerializer.html)
+    /// The component is wrapped in an unstyled parent after the newline, so its own style
+    /// still applies to it and nothing else.
+    #[must_use]
+    pub fn prepend_newline(self) -> Self {
+        Component::text("").set_children(vec![Component::newline(), self])
+    }
+
+    /// Joins components into a multi-line component, with a newline between each line.
     ///
-    /// # Behavior by variant
+    /// No newline is added after the last line. The lines are children of an unstyled
+    /// parent, so each keeps its own style without passing it on to the following lines.
+    /// No lines give an empty text component.
     ///
-    /// - `Component::String`: Returns a borrowed `&str` of the string content.
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let help = Component::lines([
+    ///     Component::text("Commands:").decoration(TextDecoration::Bold, Some(true)),
+    ///     Component::text("/spawn"),
+    ///     Component::text("/home"),
+    /// ]);
+    /// assert_eq!(help.to_plain_text(), "Commands:\n/spawn\n/home");
+    /// ```
+    pub fn lines(lines: impl IntoIterator<Item = Component>) -> Component {
+        let mut children = Vec::new();
+        for line in lines {
+            if !children.is_empty() {
+                children.push(Component::newline());
+            }
+            children.push(line);
+        }
+        if children.is_empty() {
+            return Component::empty();
+        }
+        Component::text("").set_children(children)
+    }
+
+    /// Concatenates components into one, with nothing in between.
+    ///
+    /// The result is an empty text component with the items as its `extra` children, rather
+    /// than a JSON array: an array would style every element like its first one, while
+    /// children of an unstyled parent each keep their own style. No items give an empty text
+    /// component.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let status = Component::concat([
+    ///     Component::text("Online").color(Some(Color::Named(NamedColor::Green))),
+    ///     Component::text(" | "),
+    ///     Component::text("3 players"),
+    /// ]);
+    /// assert_eq!(status.to_plain_text(), "Online | 3 players");
+    /// assert_eq!(status.get_children().len(), 3);
+    /// ```
+    pub fn concat(components: impl IntoIterator<Item = Component>) -> Component {
+        let children: Vec<_> = components.into_iter().collect();
+        if children.is_empty() {
+            return Component::empty();
+        }
+        Component::text("").set_children(children)
+    }
+
+    /// Appends a child component only if `cond` is true.
+    ///
+    /// The component is built before the call either way; use [`Self::map_if`] to avoid
+    /// constructing it when `cond` is false.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let afk = true;
+    /// let name = Component::text("Steve").append_if(afk, Component::text(" [AFK]"));
+    /// assert_eq!(name.to_plain_text(), "Steve [AFK]");
+    ///
+    /// let name = Component::text("Alex").append_if(false, Component::text(" [AFK]"));
+    /// assert_eq!(name.to_plain_text(), "Alex");
+    /// ```
+    #[must_use]
+    pub fn append_if<C: Into<Component>>(self, cond: bool, component: C) -> Self {
+        if cond { self.append(component) } else { self }
+    }
+
+    /// Applies `f` to this component only if `cond` is true, keeping builder chains fluent.
+    ///
+    /// `f` is not called when `cond` is false.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let is_admin = true;
+    /// let name = Component::text("Steve")
+    ///     .map_if(is_admin, |c| c.color(Some(Color::Named(NamedColor::Red))));
+    /// assert_eq!(name, Component::text("Steve").color(Some(Color::Named(NamedColor::Red))));
+    /// ```
+    #[must_use]
+    pub fn map_if<F: FnOnce(Self) -> Self>(self, cond: bool, f: F) -> Self {
+        if cond { f(self) } else { self }
+    }
+
+    /// Returns the "plain text" representation of this component as a [`Cow<str>`].
+    ///
+    /// This is the closest equivalent to [Kyori's plain text serializer](https://javadoc.io/doc/net.kyori/adventure-text-serializer-plain/latest/net/kyori/adventure/text/serializer/plain/PlainTextComponentSerializer.html)
+    ///
+    /// # Behavior by variant
+    ///
+    /// - `Component::String`: Returns a borrowed `&str` of the string content.
     /// - `Component::Object`:  
     ///     - If the object has `text` and no `extra` children, returns a borrowed `&str`.
     ///     - If the object has `extra` children, returns an owned `String` concatenating
@@ -659,13 +1498,162 @@ impl Component {
         }
     }
 
+    /// Returns the length of the resolved plain text (see [`Self::to_plain_text`]) in UTF-8
+    /// bytes, for limits counted in bytes such as packet and command sizes.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// assert_eq!(Component::text("Hi ").append("é").byte_len(), 5);
+    /// ```
+    pub fn byte_len(&self) -> usize {
+        self.iter_text().map(|(text, _)| text.len()).sum()
+    }
+
+    /// Returns the length of the resolved plain text (see [`Self::to_plain_text`]) in
+    /// Unicode scalar values, i.e. `char`s.
+    ///
+    /// This is what most of Minecraft's own limits count, but an emoji with a skin tone
+    /// modifier or a flag is several `char`s; use [`Self::grapheme_len`] to count what a
+    /// reader sees as one character.
+    pub fn char_len(&self) -> usize {
+        self.iter_text().map(|(text, _)| text.chars().count()).sum()
+    }
+
+    /// Returns the length of the resolved plain text (see [`Self::to_plain_text`]) in
+    /// extended grapheme clusters, so that e.g. `👍🏽` counts as one character.
+    ///
+    /// Clusters are counted over the whole plain text, so one split across two components
+    /// still counts once.
+    ///
+    /// Requires the `unicode` feature.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let reaction = Component::text("ok 👍🏽");
+    /// assert_eq!(reaction.char_len(), 5);
+    /// assert_eq!(reaction.grapheme_len(), 4);
+    /// ```
+    #[cfg(feature = "unicode")]
+    pub fn grapheme_len(&self) -> usize {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        self.to_plain_text().graphemes(true).count()
+    }
+
+    /// Returns the string if this is a [`Component::String`].
+    ///
+    /// An object's `text` isn't returned; use [`Self::get_plain_text`] to get the text of
+    /// either.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Component::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the object if this is a [`Component::Object`]
+    pub fn as_object(&self) -> Option<&ComponentObject> {
+        match self {
+            Component::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    /// Returns the object mutably if this is a [`Component::Object`]
+    pub fn as_object_mut(&mut self) -> Option<&mut ComponentObject> {
+        match self {
+            Component::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if this is a [`Component::Array`]
+    pub fn as_array(&self) -> Option<&[Component]> {
+        match self {
+            Component::Array(children) => Some(children),
+            _ => None,
+        }
+    }
+
+    /// Checks whether this is a [`Component::String`]
+    pub fn is_string(&self) -> bool {
+        matches!(self, Component::String(_))
+    }
+
+    /// Checks whether this is a [`Component::Object`]
+    pub fn is_object(&self) -> bool {
+        matches!(self, Component::Object(_))
+    }
+
+    /// Checks whether this is a [`Component::Array`]
+    pub fn is_array(&self) -> bool {
+        matches!(self, Component::Array(_))
+    }
+
+    /// Compares two components by their resolved plain text (see [`Self::to_plain_text`]).
+    ///
+    /// The comparison is case-sensitive; use [`Self::cmp_by_text_ignore_case`] otherwise.
+    pub fn cmp_by_text(&self, other: &Component) -> Ordering {
+        self.to_plain_text().cmp(&other.to_plain_text())
+    }
+
+    /// Compares two components by their lowercased plain text.
+    pub fn cmp_by_text_ignore_case(&self, other: &Component) -> Ordering {
+        self.to_plain_text()
+            .to_lowercase()
+            .cmp(&other.to_plain_text().to_lowercase())
+    }
+
+    /// Stably sorts the elements of an array, or the `extra` children of an object,
+    /// by plain text using [`Self::cmp_by_text`]. Strings are returned unchanged.
+    #[must_use]
+    pub fn sort_children_by_text(self) -> Self {
+        self.sort_children_by(Component::cmp_by_text)
+    }
+
+    /// Like [`Self::sort_children_by_text`], but ignores case.
+    #[must_use]
+    pub fn sort_children_by_text_ignore_case(self) -> Self {
+        self.sort_children_by(Component::cmp_by_text_ignore_case)
+    }
+
+    fn sort_children_by<F>(self, compare: F) -> Self
+    where
+        F: FnMut(&Component, &Component) -> Ordering,
+    {
+        match self {
+            Component::String(_) => self,
+            Component::Array(mut vec) => {
+                vec.sort_by(compare);
+                Component::Array(vec)
+            }
+            Component::Object(mut obj) => {
+                if let Some(extras) = &mut obj.extra {
+                    extras.sort_by(compare);
+                }
+                Component::Object(obj)
+            }
+        }
+    }
+
     /// Applies fallback styles to unset properties
+    ///
+    /// Every nested component gets the fallback too: `extra` children, translation arguments,
+    /// separators and hover text. Hover events that come from `fallback` itself are left as
+    /// they are.
+    ///
+    /// Array elements all get the same fallback, even though Minecraft treats the first element
+    /// as the parent of the others. A property the first element sets is then overridden by the
+    /// fallback on later elements that don't set it; use
+    /// [`apply_fallback_style_minecraft`](Self::apply_fallback_style_minecraft) to keep the
+    /// inheritance intact.
     #[must_use]
     pub fn apply_fallback_style(self, fallback: &Style) -> Self {
         match self {
             Component::String(s) => {
                 let mut obj = ComponentObject {
-                    content_type: Some(ContentType::Text),
                     text: Some(s),
                     ..Default::default()
                 };
@@ -677,22 +1665,61 @@ impl Component {
                     .map(|c| c.apply_fallback_style(fallback))
                     .collect(),
             ),
-            Component::Object(mut obj) => {
+            Component::Object(obj) => {
+                // Nested components go first, so a hover event merged in below isn't restyled
+                let mut obj = Component::Object(obj)
+                    .with_children_mapped(|c| c.apply_fallback_style(fallback))
+                    .into_object();
                 obj.merge_style(fallback);
-                if let Some(extras) = obj.extra {
-                    obj.extra = Some(
-                        extras
-                            .into_iter()
-                            .map(|c| c.apply_fallback_style(fallback))
-                            .collect(),
-                    );
+                Component::Object(Box::new(obj))
+            }
+        }
+    }
+
+    /// Applies fallback styles the way Minecraft would if this component were placed inside a
+    /// parent styled with `fallback`.
+    ///
+    /// Only the root gets the fallback, or the first element for an array, since that's the
+    /// parent of the other elements. Everything else inherits it from there, so properties set
+    /// on the first element of an array keep applying to the following elements. Hover text is
+    /// rendered on its own and doesn't inherit, so it's left as is.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let fallback = Style {
+    ///     color: Some(Color::Named(NamedColor::Gray)),
+    ///     ..Default::default()
+    /// };
+    /// let red = Some(Color::Named(NamedColor::Red));
+    /// let message = Component::Array(vec![Component::text("a").color(red.clone()), "b".into()]);
+    /// assert_eq!(
+    ///     message.apply_fallback_style_minecraft(&fallback),
+    ///     Component::Array(vec![Component::text("a").color(red), "b".into()])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn apply_fallback_style_minecraft(self, fallback: &Style) -> Self {
+        match self {
+            Component::Array(mut vec) => {
+                if let Some(first) = vec.first_mut() {
+                    *first = std::mem::take(first).apply_fallback_style_minecraft(fallback);
                 }
-                Component::Object(obj)
+                Component::Array(vec)
+            }
+            component => {
+                let mut obj = component.into_object();
+                obj.merge_style(fallback);
+                Component::Object(Box::new(obj))
             }
         }
     }
 
     /// Sets text color
+    ///
+    /// Like every style setter, this turns the component into an object first. An array
+    /// becomes its first element with the other elements as children, which is how Minecraft
+    /// reads it, so the elements keep inheriting the first element's style.
     #[must_use]
     pub fn color(self, color: Option<Color>) -> Self {
         self.map_object(|mut obj| {
@@ -710,6 +1737,15 @@ impl Component {
         })
     }
 
+    /// Sets shadow color
+    #[must_use]
+    pub fn shadow_color(self, shadow_color: Option<ShadowColor>) -> Self {
+        self.map_object(|mut obj| {
+            obj.shadow_color = shadow_color;
+            obj
+        })
+    }
+
     /// Sets text decoration state
     #[must_use]
     pub fn decoration(self, decoration: TextDecoration, state: Option<bool>) -> Self {
@@ -760,6 +1796,124 @@ impl Component {
         })
     }
 
+    /// Appends a translation argument to `with`, filling the next `%s` placeholder of
+    /// `translate`.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let join = Component::Object(Box::new(ComponentObject {
+    ///     translate: Some("multiplayer.player.joined".into()),
+    ///     ..Default::default()
+    /// }))
+    /// .with_arg(Component::text("Steve").color(Some(Color::Named(NamedColor::Yellow))));
+    /// let table = HashMap::from([(
+    ///     "multiplayer.player.joined".to_string(),
+    ///     "%s joined the game".to_string(),
+    /// )]);
+    /// assert_eq!(join.resolve_translations(&table), "Steve joined the game");
+    /// ```
+    #[must_use]
+    pub fn with_arg(self, arg: impl Into<Component>) -> Self {
+        let arg = arg.into();
+        self.map_object(|mut obj| {
+            obj.with.get_or_insert_with(Vec::new).push(arg);
+            obj
+        })
+    }
+
+    /// Appends an unstyled text translation argument, see [`Self::with_arg`]
+    #[must_use]
+    pub fn with_text_arg(self, text: impl AsRef<str>) -> Self {
+        self.with_arg(Component::String(text.as_ref().to_string()))
+    }
+
+    /// Appends several translation arguments, in order, see [`Self::with_arg`]
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let chat = Component::translatable("chat.type.text")
+    ///     .with_args([Component::text("Steve"), Component::text("Hello!")]);
+    /// assert_eq!(
+    ///     serde_json::to_string(&chat).unwrap(),
+    ///     r#"{"translate":"chat.type.text","with":[{"text":"Steve"},{"text":"Hello!"}]}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_args(self, args: impl IntoIterator<Item = Component>) -> Self {
+        self.map_object(|mut obj| {
+            obj.with.get_or_insert_with(Vec::new).extend(args);
+            obj
+        })
+    }
+
+    /// Sets or clears the text shown instead of the translation when the client has no
+    /// translation for the key, see [`Self::translatable_with_fallback`]
+    #[must_use]
+    pub fn fallback(self, fallback: Option<String>) -> Self {
+        self.map_object(|mut obj| {
+            obj.fallback = fallback;
+            obj
+        })
+    }
+
+    /// Sets or clears the separator placed between the names a selector or NBT component
+    /// resolves to. Without one, the game uses a gray `", "`, see
+    /// [`ComponentObject::effective_separator`].
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let players = Component::selector("@a").separator(Some(Component::text(" | ")));
+    /// assert_eq!(
+    ///     serde_json::to_string(&players).unwrap(),
+    ///     r#"{"selector":"@a","separator":{"text":" | "}}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn separator(self, separator: Option<Component>) -> Self {
+        self.map_object(|mut obj| {
+            obj.separator = separator.map(Box::new);
+            obj
+        })
+    }
+
+    /// Places `separator` between the names a selector or NBT component resolves to,
+    /// shorthand for [`Self::separator`]
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let players = Component::selector("@a").separated_by(" | ");
+    /// assert_eq!(players, Component::selector("@a").separator(Some(" | ".into())));
+    /// ```
+    #[must_use]
+    pub fn separated_by(self, separator: impl Into<Component>) -> Self {
+        self.separator(Some(separator.into()))
+    }
+
+    /// Sets or clears the explicit content `type`.
+    ///
+    /// Minecraft infers the type from the content field that is set, so this is rarely needed,
+    /// but some inputs carry it explicitly. [`ComponentObject::validate_content`] reports a
+    /// type that doesn't match the content.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let text = Component::text("Hi").content_type(Some(ContentType::Text));
+    /// assert_eq!(serde_json::to_string(&text).unwrap(), r#"{"type":"text","text":"Hi"}"#);
+    /// ```
+    #[must_use]
+    pub fn content_type(self, content_type: Option<ContentType>) -> Self {
+        self.map_object(|mut obj| {
+            obj.content_type = content_type;
+            obj
+        })
+    }
+
     /// Sets insertion text
     #[must_use]
     pub fn insertion(self, insertion: Option<String>) -> Self {
@@ -769,6 +1923,95 @@ impl Component {
         })
     }
 
+    /// Shows `text` when hovering, shorthand for [`HoverEvent::ShowText`]
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let concise = Component::text("Spawn")
+    ///     .hover_text("Teleport to spawn")
+    ///     .click_run("/spawn");
+    /// let verbose = Component::text("Spawn")
+    ///     .hover_event(Some(HoverEvent::ShowText {
+    ///         value: "Teleport to spawn".into(),
+    ///     }))
+    ///     .click_event(Some(ClickEvent::RunCommand {
+    ///         command: "/spawn".into(),
+    ///     }));
+    /// assert_eq!(concise, verbose);
+    /// ```
+    #[must_use]
+    pub fn hover_text(self, text: impl Into<Component>) -> Self {
+        self.hover_event(Some(HoverEvent::ShowText { value: text.into() }))
+    }
+
+    /// Runs `command` when clicked, shorthand for [`ClickEvent::RunCommand`]
+    #[must_use]
+    pub fn click_run(self, command: impl Into<String>) -> Self {
+        self.click_event(Some(ClickEvent::RunCommand {
+            command: command.into(),
+        }))
+    }
+
+    /// Suggests `command` in chat when clicked, shorthand for [`ClickEvent::SuggestCommand`]
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("Message me").click_suggest("/msg Steve ");
+    /// ```
+    #[must_use]
+    pub fn click_suggest(self, command: impl Into<String>) -> Self {
+        self.click_event(Some(ClickEvent::SuggestCommand {
+            command: command.into(),
+        }))
+    }
+
+    /// Opens `url` when clicked, shorthand for [`ClickEvent::OpenUrl`]
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let link = Component::text("Wiki")
+    ///     .click_url("https://minecraft.wiki")
+    ///     .hover_text(Component::text("Open the wiki").color(Some(Color::Named(NamedColor::Gray))));
+    /// ```
+    #[must_use]
+    pub fn click_url(self, url: impl Into<String>) -> Self {
+        self.click_event(Some(ClickEvent::OpenUrl { url: url.into() }))
+    }
+
+    /// Copies `value` to the clipboard when clicked, shorthand for [`ClickEvent::CopyToClipboard`]
+    #[must_use]
+    pub fn click_copy(self, value: impl Into<String>) -> Self {
+        self.click_event(Some(ClickEvent::CopyToClipboard {
+            value: value.into(),
+        }))
+    }
+
+    /// Opens `dialog` when clicked, shorthand for [`ClickEvent::ShowDialog`]
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let links = Component::text("Links").click_show_dialog("minecraft:server_links");
+    /// ```
+    #[must_use]
+    pub fn click_show_dialog(self, dialog: impl Into<Value>) -> Self {
+        self.click_event(Some(ClickEvent::ShowDialog {
+            dialog: dialog.into(),
+        }))
+    }
+
+    /// Sends a custom payload to the server when clicked, shorthand for [`ClickEvent::Custom`]
+    #[must_use]
+    pub fn click_custom(self, id: impl Into<String>, payload: Option<Value>) -> Self {
+        self.click_event(Some(ClickEvent::Custom {
+            id: id.into(),
+            payload,
+        }))
+    }
+
     /// Checks if a decoration is enabled
     pub fn has_decoration(&self, decoration: TextDecoration) -> bool {
         match self {
@@ -803,7 +2046,48 @@ impl Component {
         }
     }
 
-    /// Sets child components
+    /// Checks whether the whole tree is equivalent to its plain text (see
+    /// [`Self::to_plain_text`]), so it can be sent or displayed as a plain string.
+    ///
+    /// That is the case when no node, at any depth, has styling or events (see
+    /// [`Self::has_styling`]), and no node has content other than `text`: no translation,
+    /// score, selector, keybind or NBT, nor an explicit `type` other than `text`.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// assert!(Component::text("Hello ").append("world").is_plain());
+    /// assert!(!Component::text("Hi").append(Component::keybind("key.jump")).is_plain());
+    /// ```
+    pub fn is_plain(&self) -> bool {
+        self.nodes().all(|node| match node {
+            Component::Object(obj) => {
+                !node.has_styling()
+                    && obj
+                        .content_type
+                        .is_none_or(|content_type| content_type == ContentType::Text)
+                    && obj
+                        .content_fields()
+                        .iter()
+                        .all(|content_type| *content_type == ContentType::Text)
+            }
+            Component::String(_) | Component::Array(_) => true,
+        })
+    }
+
+    /// Sets child components, replacing the existing ones.
+    ///
+    /// A string becomes a text object first. An array becomes its first element, which the
+    /// other elements inherit their style from, so `children` replaces everything after the
+    /// first element, as well as that element's own children. [`Self::get_children`] lists
+    /// every element of an array instead.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let list = Component::Array(vec!["a".into(), "b".into()]).set_children(vec!["c".into()]);
+    /// assert_eq!(list, Component::text("a").append("c"));
+    /// ```
     #[must_use]
     pub fn set_children(self, children: Vec<Component>) -> Self {
         self.map_object(|mut obj| {
@@ -812,7 +2096,7 @@ impl Component {
         })
     }
 
-    /// Gets child components
+    /// Gets child components: the `extra` of an object, or every element of an array
     pub fn get_children(&self) -> &[Component] {
         match self {
             Component::Object(obj) => obj.extra.as_deref().unwrap_or_default(),
@@ -821,57 +2105,469 @@ impl Component {
         }
     }
 
-    /// Internal method to apply transformations to component objects
-    #[must_use]
-    fn map_object<F>(self, f: F) -> Self
-    where
-        F: FnOnce(ComponentObject) -> ComponentObject,
-    {
+    /// Returns the number of child components in O(1), without building a slice.
+    ///
+    /// Counts the `extra` of an object or the elements of an array; plain strings have none.
+    pub fn children_len(&self) -> usize {
         match self {
-            Component::String(s) => {
-                let obj = ComponentObject {
-                    content_type: Some(ContentType::Text),
-                    text: Some(s),
-                    ..Default::default()
-                };
-                Component::Object(Box::new(f(obj)))
-            }
-            Component::Array(vec) => {
-                let mut obj = ComponentObject {
-                    extra: Some(vec),
-                    ..Default::default()
-                };
-                obj = f(obj);
-                Component::Object(Box::new(obj))
-            }
-            Component::Object(obj) => Component::Object(Box::new(f(*obj))),
+            Component::Object(obj) => obj.extra.as_ref().map_or(0, Vec::len),
+            Component::Array(vec) => vec.len(),
+            Component::String(_) => 0,
         }
     }
-}
 
-impl ComponentObject {
-    /// Merges style properties from a fallback style
-    fn merge_style(&mut self, fallback: &Style) {
-        if self.color.is_none() {
-            self.color.clone_from(&fallback.color);
-        }
-        if self.font.is_none() {
-            self.font.clone_from(&fallback.font);
-        }
-        if self.bold.is_none() {
-            self.bold.clone_from(&fallback.bold);
-        }
-        if self.italic.is_none() {
-            self.italic = fallback.italic;
-        }
-        if self.underlined.is_none() {
-            self.underlined = fallback.underlined;
-        }
-        if self.strikethrough.is_none() {
-            self.strikethrough = fallback.strikethrough;
+    /// Returns the `i`-th child component in O(1), or `None` if out of bounds.
+    ///
+    /// Children are counted the same way as in [`Self::children_len`].
+    pub fn nth_child(&self, i: usize) -> Option<&Component> {
+        match self {
+            Component::Object(obj) => obj.extra.as_ref()?.get(i),
+            Component::Array(vec) => vec.get(i),
+            Component::String(_) => None,
         }
-        if self.obfuscated.is_none() {
-            self.obfuscated = fallback.obfuscated;
+    }
+
+    /// Deserializes a component from a JSON [`Value`].
+    ///
+    /// This stands in for `TryFrom<Value>`, which can't be implemented alongside the blanket
+    /// `From<T: AsRef<str>>` conversion.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not a valid component.
+    pub fn from_value(value: Value) -> Result<Component, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    /// Deserializes a component from a borrowed JSON [`Value`], e.g. one field of a larger
+    /// document, without cloning the whole value first.
+    ///
+    /// The strings the component keeps are still copied out of `value`; prefer
+    /// [`Component::from_value`] when you own the value and no longer need it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not a valid component.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    /// use serde_json::json;
+    ///
+    /// let packet = json!({"id": 7, "message": {"text": "Hi"}});
+    /// let message = Component::from_value_ref(&packet["message"]).unwrap();
+    /// assert_eq!(message, Component::text("Hi"));
+    /// ```
+    pub fn from_value_ref(value: &Value) -> Result<Component, serde_json::Error> {
+        Component::deserialize(value)
+    }
+
+    /// Serializes this component into a JSON [`Value`], the reverse of
+    /// [`Component::from_value`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails, which doesn't happen for components built
+    /// with this crate.
+    pub fn into_value(self) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+
+    /// Returns the length in bytes of this component serialized as compact JSON, i.e. how much
+    /// of a command's length budget it takes up in `/tellraw @a <component>`.
+    pub fn estimate_command_length(&self) -> usize {
+        json_len(self)
+    }
+
+    /// Splits this component into several components that each serialize to at most `max`
+    /// bytes, so they can be sent as separate commands (e.g. one `/tellraw` per chunk).
+    ///
+    /// Chunk boundaries only fall between top-level children (the `extra` of an object, or the
+    /// elements of an array), so a child is never cut in half. Every chunk after the first is
+    /// an empty text component carrying the root's style, so children keep inheriting the same
+    /// color, decorations and events. Arrays are treated the way Minecraft reads them: the
+    /// first element is the parent of the others.
+    ///
+    /// A single child that is longer than `max` on its own still ends up in its own, oversized
+    /// chunk. Components without children are returned as-is.
+    pub fn split_for_command(&self, max: usize) -> Vec<Component> {
+        let mut root = match self {
+            Component::String(_) => return vec![self.clone()],
+            Component::Array(vec) => match vec.split_first() {
+                Some((parent, rest)) => match parent.clone().append_all(rest.iter().cloned()) {
+                    Component::Object(obj) => *obj,
+                    other => return vec![other],
+                },
+                None => return vec![self.clone()],
+            },
+            Component::Object(obj) => (**obj).clone(),
+        };
+
+        let children = root.extra.take().unwrap_or_default();
+        if children.is_empty() || self.estimate_command_length() <= max {
+            return vec![self.clone()];
+        }
+
+        let mut continuation = ComponentObject {
+            text: Some(String::new()),
+            ..Default::default()
+        };
+        continuation.merge_style(&root.style());
+
+        let empty_len = |wrapper: &ComponentObject| {
+            let mut wrapper = wrapper.clone();
+            wrapper.extra = Some(Vec::new());
+            json_len(&Component::Object(Box::new(wrapper)))
+        };
+        let root_len = empty_len(&root);
+        let continuation_len = empty_len(&continuation);
+
+        let mut chunks = Vec::new();
+        let mut current: Vec<Component> = Vec::new();
+        let mut current_len = root_len;
+        let mut wrapper = root;
+        for child in children {
+            let child_len = json_len(&child);
+            let separator = usize::from(!current.is_empty());
+            if !current.is_empty() && current_len + separator + child_len > max {
+                let mut chunk = std::mem::replace(&mut wrapper, continuation.clone());
+                chunk.extra = Some(std::mem::take(&mut current));
+                chunks.push(Component::Object(Box::new(chunk)));
+                current_len = continuation_len + child_len;
+            } else {
+                current_len += separator + child_len;
+            }
+            current.push(child);
+        }
+        wrapper.extra = Some(current);
+        chunks.push(Component::Object(Box::new(wrapper)));
+        chunks
+    }
+
+    /// Serializes this component to a [`Value`] whose objects list their keys in Minecraft's
+    /// conventional order, for reproducible golden files and minimal diffs.
+    ///
+    /// Keys of every nested object are ordered as follows; keys not listed keep their
+    /// relative order after the listed ones:
+    ///
+    /// `action`, `type`, `text`, `translate`, `fallback`, `with`, `score`, `selector`,
+    /// `separator`, `keybind`, `nbt`, `source`, `interpret`, `block`, `entity`, `storage`,
+    /// `color`, `font`, `bold`, `italic`, `underlined`, `strikethrough`, `obfuscated`,
+    /// `shadow_color`, `insertion`, `click_event`, `hover_event`, `extra`.
+    ///
+    /// Requires the `preserve_order` feature, which makes [`serde_json::Map`] keep insertion
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization to a [`Value`] fails.
+    #[cfg(feature = "preserve_order")]
+    pub fn to_value_ordered(&self) -> Result<Value, serde_json::Error> {
+        fn reorder(value: &mut Value) {
+            match value {
+                Value::Object(map) => {
+                    let mut entries: Vec<_> = std::mem::take(map).into_iter().collect();
+                    entries.sort_by_key(|(key, _)| {
+                        ORDERED_KEYS
+                            .iter()
+                            .position(|known| known == key)
+                            .unwrap_or(ORDERED_KEYS.len())
+                    });
+                    for (key, mut value) in entries {
+                        reorder(&mut value);
+                        map.insert(key, value);
+                    }
+                }
+                Value::Array(values) => values.iter_mut().for_each(reorder),
+                _ => {}
+            }
+        }
+
+        let mut value = serde_json::to_value(self)?;
+        reorder(&mut value);
+        Ok(value)
+    }
+
+    /// Serializes this component to JSON, with `options` controlling how it's written.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let options = SerializeOptions {
+    ///     prefer_string_shorthand: true,
+    /// };
+    /// let message = Component::text("Hi ").append(Component::text("there"));
+    /// assert_eq!(message.to_json_with(options).unwrap(), r#"{"text":"Hi ","extra":["there"]}"#);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json_with(&self, options: SerializeOptions) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&*self.apply_serialize_options(options))
+    }
+
+    /// Serializes this component to a [`Value`], with `options` controlling how it's written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_value_with(&self, options: SerializeOptions) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(&*self.apply_serialize_options(options))
+    }
+
+    /// Rewrites this component the way `options` asks for, borrowing it if nothing changes
+    fn apply_serialize_options(&self, options: SerializeOptions) -> Cow<'_, Component> {
+        if !options.prefer_string_shorthand {
+            return Cow::Borrowed(self);
+        }
+        let mut component = self.clone();
+        component.collapse_bare_text();
+        Cow::Owned(component)
+    }
+
+    /// Replaces every object holding nothing but `text` with the equivalent string
+    fn collapse_bare_text(&mut self) {
+        match self {
+            Component::String(_) => {}
+            Component::Array(children) => {
+                children.iter_mut().for_each(Component::collapse_bare_text);
+            }
+            Component::Object(obj) => {
+                obj.nested_mut().for_each(Component::collapse_bare_text);
+                let text = obj.text.take();
+                if let Some(text) = text.clone()
+                    && **obj == ComponentObject::default()
+                {
+                    *self = Component::String(text);
+                } else {
+                    obj.text = text;
+                }
+            }
+        }
+    }
+
+    fn append_all(self, children: impl IntoIterator<Item = Component>) -> Self {
+        children.into_iter().fold(self, Component::append)
+    }
+
+    /// Internal method to apply transformations to component objects, converting the
+    /// component with [`Self::into_object`] first
+    #[must_use]
+    fn map_object<F>(self, f: F) -> Self
+    where
+        F: FnOnce(ComponentObject) -> ComponentObject,
+    {
+        Component::Object(Box::new(f(self.into_object())))
+    }
+
+    /// Converts this component into the object Minecraft reads it as.
+    ///
+    /// A string becomes a text object. An array becomes its first element, with the remaining
+    /// elements appended to its children, since they inherit that element's style; an empty
+    /// array becomes empty text.
+    fn into_object(self) -> ComponentObject {
+        match self {
+            Component::String(s) => ComponentObject {
+                text: Some(s),
+                ..Default::default()
+            },
+            Component::Array(vec) => {
+                let mut children = vec.into_iter();
+                let Some(first) = children.next() else {
+                    return ComponentObject {
+                        text: Some(String::new()),
+                        ..Default::default()
+                    };
+                };
+                let mut obj = first.into_object();
+                if children.len() > 0 {
+                    obj.extra.get_or_insert_with(Vec::new).extend(children);
+                }
+                obj
+            }
+            Component::Object(obj) => *obj,
+        }
+    }
+}
+
+/// Key order used by [`Component::to_value_ordered`]
+#[cfg(feature = "preserve_order")]
+static ORDERED_KEYS: &[&str] = &[
+    "action",
+    "type",
+    "text",
+    "translate",
+    "fallback",
+    "with",
+    "score",
+    "selector",
+    "separator",
+    "keybind",
+    "nbt",
+    "source",
+    "interpret",
+    "block",
+    "entity",
+    "storage",
+    "color",
+    "font",
+    "bold",
+    "italic",
+    "underlined",
+    "strikethrough",
+    "obfuscated",
+    "shadow_color",
+    "insertion",
+    "click_event",
+    "hover_event",
+    "extra",
+];
+
+/// Length of the compact JSON serialization of `component`
+fn json_len(component: &Component) -> usize {
+    serde_json::to_string(component).map_or(usize::MAX, |json| json.len())
+}
+
+impl ComponentObject {
+    /// Returns the style properties set directly on this object
+    pub(crate) fn style(&self) -> Style {
+        Style {
+            color: self.color.clone(),
+            font: self.font.clone(),
+            bold: self.bold,
+            italic: self.italic,
+            underlined: self.underlined,
+            strikethrough: self.strikethrough,
+            obfuscated: self.obfuscated,
+            shadow_color: self.shadow_color,
+            insertion: self.insertion.clone(),
+            click_event: self.click_event.clone(),
+            hover_event: self.hover_event.clone(),
+        }
+    }
+
+    /// Returns the separator placed between values when a selector or NBT component resolves
+    /// to several of them.
+    ///
+    /// This is the explicit `separator` if set, otherwise the vanilla default: a gray `", "`.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let obj = ComponentObject {
+    ///     selector: Some("@a".into()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     obj.effective_separator(),
+    ///     Component::text(", ").color(Some(Color::Named(NamedColor::Gray)))
+    /// );
+    /// ```
+    pub fn effective_separator(&self) -> Component {
+        match &self.separator {
+            Some(separator) => (**separator).clone(),
+            None => Component::text(", ").color(Some(Color::Named(NamedColor::Gray))),
+        }
+    }
+
+    /// Checks that at most one content field (`text`, `translate`, `score`, `selector`,
+    /// `keybind` or `nbt`) is set, and that an explicit `type` matches it.
+    ///
+    /// Minecraft reads only one of them and silently ignores the rest, so an object with
+    /// several is almost always a mistake. Nested components are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContentError::Multiple`] naming the populated fields if there are several,
+    /// or [`ContentError::TypeMismatch`] if `type` names a content field that isn't set.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let obj = ComponentObject {
+    ///     text: Some("Hello".into()),
+    ///     translate: Some("chat.type.text".into()),
+    ///     ..Default::default()
+    /// };
+    /// let Err(ContentError::Multiple(err)) = obj.validate_content() else {
+    ///     panic!("expected several content fields");
+    /// };
+    /// assert_eq!(err.fields(), ["text", "translate"]);
+    /// ```
+    pub fn validate_content(&self) -> Result<(), ContentError> {
+        let present = self.content_fields();
+        if present.len() > 1 {
+            return Err(ContentError::Multiple(MultipleContentError {
+                fields: present.into_iter().map(ContentType::field_name).collect(),
+            }));
+        }
+        match self.content_type {
+            Some(content_type) if !present.contains(&content_type) => {
+                Err(ContentError::TypeMismatch(content_type))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The content types whose field is set, in the order Minecraft checks them
+    fn content_fields(&self) -> Vec<ContentType> {
+        [
+            (ContentType::Text, self.text.is_some()),
+            (ContentType::Translatable, self.translate.is_some()),
+            (ContentType::Score, self.score.is_some()),
+            (ContentType::Selector, self.selector.is_some()),
+            (ContentType::Keybind, self.keybind.is_some()),
+            (ContentType::Nbt, self.nbt.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(content_type, set)| set.then_some(content_type))
+        .collect()
+    }
+
+    /// Returns the content type Minecraft reads this object as.
+    ///
+    /// An explicit `type` is returned as-is. Without one, the game looks for the first content
+    /// field that is present, in the order `text`, `translate`, `score`, `selector`, `keybind`,
+    /// then `nbt`, and ignores the others. Returns `None` when no content field is set.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let obj = ComponentObject {
+    ///     keybind: Some("key.jump".into()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(obj.inferred_content_type(), Some(ContentType::Keybind));
+    /// ```
+    pub fn inferred_content_type(&self) -> Option<ContentType> {
+        if self.content_type.is_some() {
+            return self.content_type;
+        }
+        self.content_fields().first().copied()
+    }
+
+    /// Merges style properties from a fallback style
+    fn merge_style(&mut self, fallback: &Style) {
+        if self.color.is_none() {
+            self.color.clone_from(&fallback.color);
+        }
+        if self.font.is_none() {
+            self.font.clone_from(&fallback.font);
+        }
+        if self.bold.is_none() {
+            self.bold.clone_from(&fallback.bold);
+        }
+        if self.italic.is_none() {
+            self.italic = fallback.italic;
+        }
+        if self.underlined.is_none() {
+            self.underlined = fallback.underlined;
+        }
+        if self.strikethrough.is_none() {
+            self.strikethrough = fallback.strikethrough;
+        }
+        if self.obfuscated.is_none() {
+            self.obfuscated = fallback.obfuscated;
         }
         if self.shadow_color.is_none() {
             self.shadow_color = fallback.shadow_color;
@@ -888,6 +2584,15 @@ impl ComponentObject {
     }
 }
 
+/// Options for [`Component::to_json_with`] and [`Component::to_value_with`], for matching
+/// the JSON a particular server or tool emits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SerializeOptions {
+    /// Writes text nodes without style, events or children as bare strings, `"x"` instead of
+    /// `{"text":"x"}`, anywhere in the tree. Minecraft reads both the same way.
+    pub prefer_string_shorthand: bool,
+}
+
 /// Error type for color parsing
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ParseColorError;
@@ -900,9 +2605,61 @@ impl std::fmt::Display for ParseColorError {
 
 impl std::error::Error for ParseColorError {}
 
+/// Error returned by [`ComponentObject::validate_content`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentError {
+    /// Several content fields are set, and only one of them is used
+    Multiple(MultipleContentError),
+    /// The explicit `type` names a content field that isn't set
+    TypeMismatch(ContentType),
+}
+
+impl std::fmt::Display for ContentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContentError::Multiple(err) => err.fmt(f),
+            ContentError::TypeMismatch(content_type) => write!(
+                f,
+                "component type requires the {} field, which is not set",
+                content_type.field_name()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ContentError {}
+
+/// The content fields found by [`ComponentObject::validate_content`] when several are set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipleContentError {
+    fields: Vec<&'static str>,
+}
+
+impl MultipleContentError {
+    /// The populated content fields, in the order `text`, `translate`, `score`, `selector`,
+    /// `keybind`, `nbt`
+    pub fn fields(&self) -> &[&'static str] {
+        &self.fields
+    }
+}
+
+impl std::fmt::Display for MultipleContentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "component has several content fields ({}), only one is used",
+            self.fields.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for MultipleContentError {}
+
 fn parse_hex_color(s: &str) -> Option<[u8; 3]> {
     let s = s.strip_prefix('#')?;
-    if s.len() == 6 {
+    // `from_str_radix` alone would accept a sign like `+1`. Hex digits are ASCII, which also
+    // keeps the slicing below on char boundaries
+    if s.len() == 6 && s.bytes().all(|b| b.is_ascii_hexdigit()) {
         let r = u8::from_str_radix(&s[0..2], 16).ok()?;
         let g = u8::from_str_radix(&s[2..4], 16).ok()?;
         let b = u8::from_str_radix(&s[4..6], 16).ok()?;
@@ -926,6 +2683,21 @@ impl FromStr for Color {
     }
 }
 
+/// Compares the component's plain text (see [`Component::to_plain_text`]) to a string,
+/// ignoring styling, so `assert_eq!(component, "Hello")` works in tests.
+impl PartialEq<str> for Component {
+    fn eq(&self, other: &str) -> bool {
+        self.to_plain_text() == other
+    }
+}
+
+/// Compares the component's plain text to a string, like `PartialEq<str>`.
+impl PartialEq<&str> for Component {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
 impl<T: AsRef<str>> From<T> for Component {
     fn from(value: T) -> Component {
         let s: &str = value.as_ref();
@@ -933,6 +2705,85 @@ impl<T: AsRef<str>> From<T> for Component {
     }
 }
 
+/// An empty plain string, `Component::String(String::new())`, which serializes to `""`.
+///
+/// This is the smallest valid component and renders as nothing; use [`Component::text`] for
+/// an empty object that can carry styling.
+impl Default for Component {
+    fn default() -> Self {
+        Component::String(String::new())
+    }
+}
+
+/// Parses a component from its JSON text.
+///
+/// This is JSON only: `"<red>hi".parse::<Component>()` fails. MiniMessage input has its own
+/// entry point in the `minimessage` module. Note that `Component::from("...")` does not parse
+/// anything either, it creates a plain string component.
+///
+/// ```
+/// use kyori_component_json::*;
+///
+/// let component: Component = r#"{"text":"Hello","color":"gold"}"#.parse().unwrap();
+/// assert_eq!(component, Component::text("Hello").color(Some(Color::Named(NamedColor::Gold))));
+/// ```
+impl FromStr for Component {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Appends `rhs` as a child, exactly like [`Component::append`].
+///
+/// `a + b + c` is left-associative, so it yields `a` with `b` and `c` as children rather than
+/// nesting `c` inside `b`. Each `+` may allocate when the left-hand side has to be promoted
+/// to an object or its children grow.
+///
+/// ```
+/// use kyori_component_json::Component;
+///
+/// let message = Component::text("Hello") + ", " + Component::text("world");
+/// assert_eq!(message.to_plain_text(), "Hello, world");
+/// ```
+impl<C: Into<Component>> Add<C> for Component {
+    type Output = Component;
+
+    fn add(self, rhs: C) -> Component {
+        self.append(rhs)
+    }
+}
+
+/// Appends `rhs` as a child in place, exactly like [`Component::append`].
+impl<C: Into<Component>> AddAssign<C> for Component {
+    fn add_assign(&mut self, rhs: C) {
+        let this = std::mem::replace(self, Component::String(String::new()));
+        *self = this.append(rhs);
+    }
+}
+
+/// Appends each item as a child, exactly like calling [`Component::append`] repeatedly.
+///
+/// - `Component::String` is promoted to an object with the string as its text and the items
+///   as `extra`.
+/// - `Component::Array` gets the items pushed as further elements.
+/// - `Component::Object` gets the items pushed onto its `extra` children.
+///
+/// ```
+/// use kyori_component_json::Component;
+///
+/// let mut list = Component::text("list:");
+/// list.extend(["a", "b"].map(Component::text));
+/// assert_eq!(list.to_plain_text(), "list:ab");
+/// ```
+impl Extend<Component> for Component {
+    fn extend<I: IntoIterator<Item = Component>>(&mut self, iter: I) {
+        let this = std::mem::replace(self, Component::String(String::new()));
+        *self = this.append_all(iter);
+    }
+}
+
 impl fmt::Display for NamedColor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
@@ -1055,4 +2906,1225 @@ mod tests {
             }));
         assert_eq!(component_full, expected_full);
     }
+
+    #[test]
+    fn test_component_macro_content_and_no_arg_arms() {
+        let shadow = ShadowColor::from_rgba([0, 0, 0, 255]);
+        let component = component!(text: "Press", {
+            shadow_color: shadow,
+            append_space,
+            append: (component!(keybind: "key.jump", { color: gold })),
+            append_newline,
+            append: (component!(translate: "options.jump")),
+            append_space
+        });
+        let expected = Component::text("Press")
+            .shadow_color(Some(shadow))
+            .append_space()
+            .append(Component::keybind("key.jump").color(Some(Color::Named(NamedColor::Gold))))
+            .append_newline()
+            .append(Component::translatable("options.jump"))
+            .append_space();
+        assert_eq!(component, expected);
+
+        assert_eq!(
+            component!(translate: "chat.type.text", { append_newline }),
+            Component::translatable("chat.type.text").append_newline()
+        );
+        assert_eq!(
+            component!(keybind: "key.sneak", { shadow_color: shadow }),
+            Component::keybind("key.sneak").shadow_color(Some(shadow))
+        );
+        assert_eq!(
+            serde_json::to_value(component!(keybind: "key.sneak")).unwrap(),
+            serde_json::json!({"keybind": "key.sneak"})
+        );
+    }
+
+    #[test]
+    fn test_builders_omit_text_type() {
+        let appended = Component::String("Hello ".to_string()).append("world");
+        assert_eq!(
+            serde_json::to_value(&appended).unwrap(),
+            serde_json::json!({"text": "Hello ", "extra": ["world"]})
+        );
+
+        let colored =
+            Component::String("hi".to_string()).color(Some(Color::Named(NamedColor::Red)));
+        assert_eq!(
+            serde_json::to_value(&colored).unwrap(),
+            serde_json::json!({"text": "hi", "color": "red"})
+        );
+
+        let fallback = Style {
+            bold: Some(true),
+            ..Default::default()
+        };
+        let styled = Component::String("hi".to_string()).apply_fallback_style(&fallback);
+        assert_eq!(
+            serde_json::to_value(&styled).unwrap(),
+            serde_json::json!({"text": "hi", "bold": true})
+        );
+    }
+
+    #[test]
+    fn test_sort_children_by_text() {
+        let list = Component::Array(vec![
+            Component::text("charlie").color(Some(Color::Named(NamedColor::Red))),
+            Component::text("Bravo").color(Some(Color::Named(NamedColor::Green))),
+            Component::text("alpha").color(Some(Color::Named(NamedColor::Blue))),
+        ]);
+
+        let sorted = list.clone().sort_children_by_text();
+        let order: Vec<_> = sorted
+            .get_children()
+            .iter()
+            .map(|c| c.to_plain_text())
+            .collect();
+        assert_eq!(order, ["Bravo", "alpha", "charlie"]);
+
+        let sorted = list.sort_children_by_text_ignore_case();
+        let order: Vec<_> = sorted
+            .get_children()
+            .iter()
+            .map(|c| c.to_plain_text())
+            .collect();
+        assert_eq!(order, ["alpha", "Bravo", "charlie"]);
+        // Styles travel with their text
+        assert_eq!(
+            sorted.get_children()[0],
+            Component::text("alpha").color(Some(Color::Named(NamedColor::Blue)))
+        );
+    }
+
+    #[test]
+    fn test_split_for_command() {
+        let parts: Vec<Component> = (0..40)
+            .map(|i| {
+                Component::text(format!("part {i} ")).color(Some(Color::Named(NamedColor::Aqua)))
+            })
+            .collect();
+        let message = Component::Array(parts)
+            .color(Some(Color::Named(NamedColor::Gold)))
+            .decoration(TextDecoration::Bold, Some(true));
+        assert!(message.estimate_command_length() > 300);
+
+        let chunks = message.split_for_command(300);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.estimate_command_length() <= 300);
+            // Continuation chunks keep the root style for their children to inherit
+            assert!(chunk.has_decoration(TextDecoration::Bold));
+        }
+        let joined: String = chunks.iter().map(|c| c.to_plain_text()).collect();
+        assert_eq!(joined, message.to_plain_text());
+
+        let short = Component::text("short");
+        assert_eq!(short.split_for_command(300), [short]);
+    }
+
+    #[test]
+    fn test_score_value_round_trip() {
+        let component = Component::Object(Box::new(ComponentObject {
+            score: Some(ScoreContent::new("@s", "kills").value("42")),
+            ..Default::default()
+        }));
+        let json = serde_json::json!({
+            "score": {"name": "@s", "objective": "kills", "value": "42"}
+        });
+        assert_eq!(serde_json::to_value(&component).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<Component>(json).unwrap(),
+            component
+        );
+
+        let live = ScoreContent::new("@s", "kills");
+        assert_eq!(
+            serde_json::to_value(&live).unwrap(),
+            serde_json::json!({"name": "@s", "objective": "kills"})
+        );
+    }
+
+    #[test]
+    fn test_effective_separator() {
+        let mut obj = ComponentObject {
+            selector: Some("@a".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(obj.effective_separator()).unwrap(),
+            serde_json::json!({"text": ", ", "color": "gray"})
+        );
+
+        obj.separator = Some(Box::new(Component::text(" | ")));
+        assert_eq!(obj.effective_separator(), Component::text(" | "));
+
+        let players = Component::selector("@a").separated_by(Component::text(" | "));
+        assert_eq!(
+            players.as_object().unwrap().effective_separator(),
+            Component::text(" | ")
+        );
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut string_root = Component::String("list:".to_string());
+        string_root.extend(vec![Component::text("a"), Component::text("b")]);
+        assert_eq!(
+            serde_json::to_value(&string_root).unwrap(),
+            serde_json::json!({"text": "list:", "extra": [{"text": "a"}, {"text": "b"}]})
+        );
+
+        let mut object_root = Component::text("list:").color(Some(Color::Named(NamedColor::Red)));
+        object_root.extend(vec![Component::text("a")]);
+        object_root.extend(vec![Component::text("b")]);
+        assert_eq!(
+            object_root,
+            Component::text("list:")
+                .color(Some(Color::Named(NamedColor::Red)))
+                .append(Component::text("a"))
+                .append(Component::text("b"))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "preserve_order")]
+    fn test_to_value_ordered() {
+        let component = Component::text("Hello ")
+            .append(Component::text("world").click_run("/hi"))
+            .color(Some(Color::Named(NamedColor::Yellow)))
+            .hover_text("hover")
+            .decoration(TextDecoration::Bold, Some(true));
+        let value = component.to_value_ordered().unwrap();
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"text":"Hello ","color":"yellow","bold":true,"hover_event":{"action":"show_text","value":"hover"},"extra":[{"text":"world","click_event":{"action":"run_command","command":"/hi"}}]}"#
+        );
+    }
+
+    #[test]
+    fn test_shadow_color_floats() {
+        let shadow = ShadowColor::Floats([1.0 / 3.0, 1.5, -0.25, 0.5]);
+        assert_eq!(
+            serde_json::to_string(&shadow).unwrap(),
+            "[0.33333,1.0,0.0,0.5]"
+        );
+        assert_eq!(
+            serde_json::to_string(&ShadowColor::Int(-16777216)).unwrap(),
+            "-16777216"
+        );
+
+        let parsed: ShadowColor = serde_json::from_str("[0.25,0.5,0.75,1.0]").unwrap();
+        assert_eq!(parsed, ShadowColor::Floats([0.25, 0.5, 0.75, 1.0]));
+        assert!(serde_json::from_str::<ShadowColor>("[0.25,0.5,0.75,1.5]").is_err());
+        assert!(
+            serde_json::from_str::<Component>(r#"{"text":"x","shadow_color":[2,0,0,1]}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let parsed: Component = r#"{"text":"hi","bold":true}"#.parse().unwrap();
+        assert_eq!(
+            parsed,
+            Component::text("hi").decoration(TextDecoration::Bold, Some(true))
+        );
+        assert_eq!(
+            "\"hi\"".parse::<Component>().unwrap(),
+            Component::from("hi")
+        );
+        assert!("{\"text\":".parse::<Component>().is_err());
+        assert!("<red>hi".parse::<Component>().is_err());
+
+        let value = serde_json::json!(["a", {"text": "b"}]);
+        assert_eq!(
+            Component::from_value(value).unwrap(),
+            Component::Array(vec![Component::from("a"), Component::text("b")])
+        );
+        assert!(Component::from_value(serde_json::json!({"text": 1, "bogus": 2})).is_err());
+    }
+
+    // Boxing `ShowText::value` would not shrink `HoverEvent`: `ShowItem` and `ShowEntity` are
+    // larger than the `Component` it holds
+    #[test]
+    fn test_type_sizes() {
+        assert!(size_of::<HoverEvent>() > size_of::<Component>());
+    }
+
+    #[test]
+    fn test_indexed_children() {
+        let object = Component::text("a").append("b").append("c");
+        assert_eq!(object.children_len(), 2);
+        assert_eq!(object.nth_child(1), Some(&Component::from("c")));
+        assert_eq!(object.nth_child(2), None);
+        assert_eq!(Component::text("a").children_len(), 0);
+        assert_eq!(Component::text("a").nth_child(0), None);
+
+        let array = Component::Array(vec![Component::from("x"), Component::from("y")]);
+        assert_eq!(array.children_len(), 2);
+        assert_eq!(array.nth_child(0), Some(&Component::from("x")));
+        assert_eq!(array.nth_child(5), None);
+
+        let string = Component::from("s");
+        assert_eq!(string.children_len(), 0);
+        assert_eq!(string.nth_child(0), None);
+    }
+
+    #[test]
+    fn test_color_lerp_and_blend() {
+        let red = Color::Hex("#FF0000".into());
+        let blue = Color::Hex("#0000FF".into());
+        assert_eq!(red.lerp(&blue, 0.5), Color::Hex("#800080".into()));
+        assert_eq!(red.lerp(&blue, -1.0), Color::Hex("#FF0000".into()));
+        assert_eq!(red.lerp(&blue, 2.0), Color::Hex("#0000FF".into()));
+
+        let named = Color::Named(NamedColor::Black).lerp(&Color::Named(NamedColor::White), 1.0);
+        assert_eq!(named, Color::Hex("#FFFFFF".into()));
+        assert_eq!(
+            Color::Named(NamedColor::Red).rgb(),
+            Some([0xFF, 0x55, 0x55])
+        );
+
+        let invalid = Color::Hex("not a color".into());
+        assert_eq!(invalid.rgb(), None);
+        assert_eq!(invalid.lerp(&red, 0.5), invalid);
+
+        assert_eq!(red.blend(&blue, 1.0), Color::Hex("#FF0000".into()));
+        assert_eq!(red.blend(&blue, 0.25), Color::Hex("#4000BF".into()));
+    }
+
+    #[test]
+    fn test_primitive_shorthand() {
+        for (json, text) in [
+            ("true", "true"),
+            ("false", "false"),
+            ("42", "42"),
+            ("3.14", "3.14"),
+        ] {
+            let component: Component = serde_json::from_str(json).unwrap();
+            assert_eq!(component, Component::String(text.to_string()));
+            assert_eq!(
+                serde_json::to_string(&component).unwrap(),
+                format!("\"{text}\"")
+            );
+        }
+
+        let mixed: Component = serde_json::from_str(r#"["Score: ", 5, {"text": "!"}]"#).unwrap();
+        assert_eq!(mixed.to_plain_text(), "Score: 5!");
+        assert!(serde_json::from_str::<Component>("null").is_err());
+    }
+
+    #[test]
+    fn test_append_and_style_array_root() {
+        let red = || Some(Color::Named(NamedColor::Red));
+        let array = Component::Array(vec![Component::text("a").color(red()), "b".into()]);
+
+        // Appending keeps the array, the new element is styled like the first one
+        let appended = array.clone().append("c");
+        assert_eq!(appended.children_len(), 3);
+        assert!(matches!(appended, Component::Array(_)));
+
+        // Styling makes the first element the parent, so "b" and "c" still inherit its color
+        let styled = appended.decoration(TextDecoration::Bold, Some(true));
+        let expected = Component::text("a")
+            .color(red())
+            .decoration(TextDecoration::Bold, Some(true))
+            .append("b")
+            .append("c");
+        assert_eq!(styled, expected);
+        assert_eq!(styled.to_plain_text(), "abc");
+
+        // Styling again and appending afterwards behave like any object root
+        let chained = array.color(None).append("d").color(red());
+        assert_eq!(
+            chained,
+            Component::text("a").color(red()).append("b").append("d")
+        );
+
+        assert_eq!(
+            Component::Array(vec![]).color(red()),
+            Component::text("").color(red())
+        );
+    }
+
+    #[test]
+    fn test_integer_color() {
+        let color: Color = serde_json::from_str("16755200").unwrap();
+        assert_eq!(color, Color::Hex("#FFAA00".into()));
+        assert_eq!(serde_json::to_string(&color).unwrap(), r##""#FFAA00""##);
+        assert_eq!(
+            serde_json::from_str::<Color>("0").unwrap(),
+            Color::Hex("#000000".into())
+        );
+
+        let component: Component = serde_json::from_str(r#"{"text":"a","color":255}"#).unwrap();
+        assert_eq!(
+            component,
+            Component::text("a").color(Some(Color::Hex("#0000FF".into())))
+        );
+
+        assert!(serde_json::from_str::<Color>("16777216").is_err());
+        assert!(serde_json::from_str::<Color>("-1").is_err());
+    }
+
+    #[test]
+    fn test_validate_content() {
+        assert!(ComponentObject::default().validate_content().is_ok());
+        let text = ComponentObject {
+            text: Some("a".into()),
+            ..Default::default()
+        };
+        assert!(text.validate_content().is_ok());
+
+        let mixed = ComponentObject {
+            score: Some(ScoreContent::new("@p", "kills")),
+            keybind: Some("key.jump".into()),
+            nbt: Some("Health".into()),
+            ..text
+        };
+        let ContentError::Multiple(err) = mixed.validate_content().unwrap_err() else {
+            panic!("expected several content fields");
+        };
+        assert_eq!(err.fields(), ["text", "score", "keybind", "nbt"]);
+        assert_eq!(
+            err.to_string(),
+            "component has several content fields (text, score, keybind, nbt), only one is used"
+        );
+    }
+
+    #[test]
+    fn test_content_type_literal_alias() {
+        let component: Component =
+            serde_json::from_str(r#"{"type":"literal","text":"Hi"}"#).unwrap();
+        assert_eq!(
+            component,
+            Component::text("Hi").content_type(Some(ContentType::Text))
+        );
+        assert!(component.as_object().unwrap().validate_content().is_ok());
+        assert_eq!(
+            serde_json::to_string(&component).unwrap(),
+            r#"{"type":"text","text":"Hi"}"#
+        );
+    }
+
+    #[test]
+    fn test_content_type_builder() {
+        let typed = Component::text("a").content_type(Some(ContentType::Text));
+        let Component::Object(obj) = &typed else {
+            panic!("expected an object");
+        };
+        assert_eq!(obj.content_type, Some(ContentType::Text));
+        assert!(obj.validate_content().is_ok());
+
+        let cleared = typed.content_type(None);
+        assert_eq!(cleared, Component::text("a"));
+
+        let mismatched = Component::text("a").content_type(Some(ContentType::Keybind));
+        let Component::Object(obj) = &mismatched else {
+            panic!("expected an object");
+        };
+        let err = obj.validate_content().unwrap_err();
+        assert_eq!(err, ContentError::TypeMismatch(ContentType::Keybind));
+        assert_eq!(
+            err.to_string(),
+            "component type requires the keybind field, which is not set"
+        );
+    }
+
+    #[test]
+    fn test_parse_hex_rejects_non_ascii() {
+        // Six bytes, but slicing them in pairs would split the 'é'
+        assert_eq!(Color::parse_hex("#1é234"), None);
+        assert!("#1é234".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn test_parse_hex_rejects_signs() {
+        // Each pair would parse on its own as a signed number
+        assert_eq!(Color::parse_hex("#+1+2+3"), None);
+        assert!("#+1+2+3".parse::<Color>().is_err());
+        assert_eq!(Color::parse_hex("#-1ABCD"), None);
+    }
+
+    #[test]
+    fn test_fallback_style_reaches_hover_and_args() {
+        let red = || Some(Color::Named(NamedColor::Red));
+        let fallback = Style {
+            color: red(),
+            ..Default::default()
+        };
+        let translatable = ComponentObject {
+            translate: Some("chat.type.text".into()),
+            with: Some(vec![Component::text("arg")]),
+            ..Default::default()
+        };
+        let message = Component::text("a")
+            .hover_text("hover")
+            .append(Component::Object(Box::new(translatable)));
+
+        let styled = message.apply_fallback_style(&fallback);
+        assert!(styled.nodes().all(|node| match node {
+            Component::Object(obj) => obj.color == red(),
+            Component::String(_) | Component::Array(_) => false,
+        }));
+
+        // A hover event taken from the fallback keeps its own text unstyled
+        let with_hover = Style {
+            hover_event: Some(HoverEvent::ShowText {
+                value: Component::text("tip"),
+            }),
+            ..fallback
+        };
+        let styled = Component::text("b").apply_fallback_style(&with_hover);
+        assert!(matches!(
+            &styled,
+            Component::Object(obj) if obj.hover_event == with_hover.hover_event
+        ));
+    }
+
+    #[test]
+    fn test_default_component() {
+        let component = Component::default();
+        assert_eq!(component, Component::String(String::new()));
+        assert_eq!(serde_json::to_string(&component).unwrap(), r#""""#);
+        assert_eq!(component.to_plain_text(), "");
+
+        #[derive(Default)]
+        struct Sign {
+            front: [Component; 4],
+        }
+        assert!(
+            Sign::default()
+                .front
+                .iter()
+                .all(|line| line.to_plain_text().is_empty())
+        );
+    }
+
+    #[test]
+    fn test_inferred_content_type() {
+        let cases = [
+            (
+                ComponentObject {
+                    text: Some("hi".into()),
+                    ..Default::default()
+                },
+                ContentType::Text,
+            ),
+            (
+                ComponentObject {
+                    translate: Some("chat.type.text".into()),
+                    ..Default::default()
+                },
+                ContentType::Translatable,
+            ),
+            (
+                ComponentObject {
+                    score: Some(ScoreContent::new("@s", "kills")),
+                    ..Default::default()
+                },
+                ContentType::Score,
+            ),
+            (
+                ComponentObject {
+                    selector: Some("@p".into()),
+                    ..Default::default()
+                },
+                ContentType::Selector,
+            ),
+            (
+                ComponentObject {
+                    keybind: Some("key.jump".into()),
+                    ..Default::default()
+                },
+                ContentType::Keybind,
+            ),
+            (
+                ComponentObject {
+                    nbt: Some("Health".into()),
+                    entity: Some("@s".into()),
+                    ..Default::default()
+                },
+                ContentType::Nbt,
+            ),
+        ];
+        for (obj, expected) in cases {
+            assert_eq!(obj.inferred_content_type(), Some(expected));
+        }
+
+        assert_eq!(ComponentObject::default().inferred_content_type(), None);
+        // Earlier fields win, and an explicit type wins over everything
+        let both = ComponentObject {
+            selector: Some("@p".into()),
+            translate: Some("key".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            both.inferred_content_type(),
+            Some(ContentType::Translatable)
+        );
+        let explicit = ComponentObject {
+            content_type: Some(ContentType::Selector),
+            ..both
+        };
+        assert_eq!(
+            explicit.inferred_content_type(),
+            Some(ContentType::Selector)
+        );
+    }
+
+    #[test]
+    fn test_shadow_color_eq_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of(shadow: &ShadowColor) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            shadow.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = ShadowColor::Floats([0.25, 0.5, 0.75, 1.0]);
+        let b = ShadowColor::Floats([0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        let zero = ShadowColor::Floats([0.0; 4]);
+        let negative_zero = ShadowColor::Floats([-0.0; 4]);
+        assert_eq!(zero, negative_zero);
+        assert_eq!(hash_of(&zero), hash_of(&negative_zero));
+
+        let nan = ShadowColor::Floats([f32::NAN; 4]);
+        assert_eq!(nan, nan);
+        assert_eq!(hash_of(&nan), hash_of(&ShadowColor::Floats([-f32::NAN; 4])));
+
+        assert_ne!(a, ShadowColor::Floats([0.25, 0.5, 0.75, 0.0]));
+        assert_ne!(ShadowColor::Int(0), zero);
+        assert_eq!(
+            std::collections::HashSet::from([a, b, ShadowColor::Int(-1)]).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_from_sections() {
+        let style = |color| Style {
+            color: Some(Color::Named(color)),
+            bold: (color == NamedColor::Red).then_some(true),
+            ..Default::default()
+        };
+        let message = Component::from_sections([
+            ("Red ".to_string(), style(NamedColor::Red)),
+            ("blue".to_string(), style(NamedColor::Blue)),
+        ]);
+        assert_eq!(
+            message,
+            Component::Array(vec![
+                Component::default(),
+                Component::text("Red ")
+                    .color(Some(Color::Named(NamedColor::Red)))
+                    .decoration(TextDecoration::Bold, Some(true)),
+                Component::text("blue").color(Some(Color::Named(NamedColor::Blue))),
+            ])
+        );
+        // The bold red section doesn't make the blue one bold
+        let runs = message.styled_runs();
+        assert_eq!(runs[1].1.bold, Some(true));
+        assert_eq!(runs[2].1.bold, None);
+    }
+
+    #[test]
+    fn test_event_actions_match_serialized_tags() {
+        let clicks = [
+            ClickEvent::OpenUrl { url: "u".into() },
+            ClickEvent::OpenFile { path: "p".into() },
+            ClickEvent::RunCommand {
+                command: "/c".into(),
+            },
+            ClickEvent::SuggestCommand {
+                command: "/c".into(),
+            },
+            ClickEvent::ChangePage { page: 1 },
+            ClickEvent::CopyToClipboard { value: "v".into() },
+        ];
+        let actions: Vec<_> = clicks.iter().map(ClickEvent::action).collect();
+        assert_eq!(
+            actions,
+            [
+                "open_url",
+                "open_file",
+                "run_command",
+                "suggest_command",
+                "change_page",
+                "copy_to_clipboard"
+            ]
+        );
+
+        let hovers = [
+            HoverEvent::ShowText {
+                value: Component::text("t"),
+            },
+            HoverEvent::ShowItem {
+                id: "minecraft:stone".into(),
+                count: None,
+                components: None,
+            },
+            HoverEvent::ShowEntity {
+                name: None,
+                id: "minecraft:pig".into(),
+                uuid: UuidRepr::IntArray([0; 4]),
+            },
+        ];
+        let actions: Vec<_> = hovers.iter().map(HoverEvent::action).collect();
+        assert_eq!(actions, ["show_text", "show_item", "show_entity"]);
+
+        for (action, json) in clicks
+            .iter()
+            .map(|c| (c.action(), serde_json::to_value(c).unwrap()))
+            .chain(
+                hovers
+                    .iter()
+                    .map(|h| (h.action(), serde_json::to_value(h).unwrap())),
+            )
+        {
+            assert_eq!(json["action"], action);
+        }
+    }
+
+    #[test]
+    fn test_fallback_style_array_inheritance() {
+        let fallback = Style {
+            color: Some(Color::Named(NamedColor::Gray)),
+            bold: Some(true),
+            ..Default::default()
+        };
+        let red = Some(Color::Named(NamedColor::Red));
+        let message = Component::Array(vec![
+            Component::text("a").color(red.clone()),
+            Component::from("b"),
+            Component::text("c").decoration(TextDecoration::Bold, Some(false)),
+        ]);
+        let colors = |c: &Component| {
+            c.styled_runs()
+                .into_iter()
+                .map(|(text, style)| (text.to_string(), style.color, style.bold))
+                .collect::<Vec<_>>()
+        };
+
+        // Every element gets the fallback, so "b" turns gray instead of inheriting red
+        let uniform = message.clone().apply_fallback_style(&fallback);
+        assert_eq!(
+            colors(&uniform),
+            vec![
+                ("a".into(), red.clone(), Some(true)),
+                ("b".into(), fallback.color.clone(), Some(true)),
+                ("c".into(), fallback.color.clone(), Some(false)),
+            ]
+        );
+
+        // Only the first element gets it, and the rest inherit from the first
+        let inherited = message.apply_fallback_style_minecraft(&fallback);
+        assert_eq!(
+            colors(&inherited),
+            vec![
+                ("a".into(), red.clone(), Some(true)),
+                ("b".into(), red.clone(), Some(true)),
+                ("c".into(), red, Some(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text_owned_reuses_buffer() {
+        let text = String::from("no copy");
+        let buffer = text.as_ptr();
+        let Component::Object(obj) = Component::text_owned(text) else {
+            panic!("expected an object");
+        };
+        assert_eq!(obj.text.as_deref().map(str::as_ptr), Some(buffer));
+    }
+
+    #[test]
+    fn test_eq_plain_text() {
+        let message = Component::text("Hello, ")
+            .color(Some(Color::Named(NamedColor::Red)))
+            .append(Component::text("world").decoration(TextDecoration::Bold, Some(true)));
+        assert_eq!(message, "Hello, world");
+        assert!(message == *"Hello, world");
+        assert_ne!(message, "Hello, ");
+        assert_ne!(message, "hello, world");
+        assert_eq!(Component::Array(vec!["a".into(), "b".into()]), "ab");
+    }
+
+    #[test]
+    fn test_variant_accessors() {
+        let mut string = Component::from("plain");
+        let mut object = Component::text("text");
+        let mut array = Component::Array(vec!["a".into(), "b".into()]);
+
+        assert_eq!(string.as_str(), Some("plain"));
+        assert_eq!(object.as_str(), None);
+        assert_eq!(array.as_str(), None);
+
+        assert!(string.as_object().is_none());
+        assert_eq!(
+            object.as_object().and_then(|obj| obj.text.as_deref()),
+            Some("text")
+        );
+        assert!(array.as_object().is_none());
+
+        assert!(string.as_object_mut().is_none());
+        assert!(array.as_object_mut().is_none());
+        if let Some(obj) = object.as_object_mut() {
+            obj.bold = Some(true);
+        }
+        assert!(object.has_decoration(TextDecoration::Bold));
+
+        assert!(string.as_array().is_none());
+        assert!(object.as_array().is_none());
+        assert_eq!(array.as_array().map(<[_]>::len), Some(2));
+
+        assert!(string.is_string() && !string.is_object() && !string.is_array());
+        assert!(object.is_object() && !object.is_string() && !object.is_array());
+        assert!(array.is_array() && !array.is_string() && !array.is_object());
+    }
+
+    #[test]
+    fn test_translation_args() {
+        let parsed: Component =
+            serde_json::from_str(r#"{"translate":"x","with":[5,"y"]}"#).unwrap();
+        let built = Component::Object(Box::new(ComponentObject {
+            translate: Some("x".into()),
+            ..Default::default()
+        }))
+        .with_text_arg("5")
+        .with_arg("y");
+        assert_eq!(parsed, built);
+        assert_eq!(
+            serde_json::to_string(&built).unwrap(),
+            r#"{"translate":"x","with":["5","y"]}"#
+        );
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let component = Component::text("Hi ")
+            .color(Some(Color::Named(NamedColor::Gold)))
+            .append(Component::translatable("item.minecraft.diamond").with_text_arg("x"));
+        let value = component.clone().into_value().unwrap();
+        assert_eq!(value, serde_json::to_value(&component).unwrap());
+
+        assert_eq!(Component::from_value_ref(&value).unwrap(), component);
+        assert_eq!(Component::from_value(value).unwrap(), component);
+
+        let wrapper = serde_json::json!({"chat": ["a", {"text": "b", "bold": true}]});
+        assert_eq!(
+            Component::from_value_ref(&wrapper["chat"]).unwrap(),
+            Component::Array(vec![
+                "a".into(),
+                Component::text("b").decoration(TextDecoration::Bold, Some(true)),
+            ])
+        );
+        assert!(Component::from_value_ref(&Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_text_lengths() {
+        // A family emoji is 5 scalars joined by zero-width joiners, a flag is 2 regional
+        // indicators, and "é" here is "e" plus a combining accent
+        let message = Component::text("Hi ")
+            .append(Component::text("👨‍👩‍👧").color(Some(Color::Named(NamedColor::Red))))
+            .append(Component::Array(vec!["🇫🇷".into(), "e\u{301}".into()]))
+            .hover_text("not counted");
+
+        assert_eq!(message.byte_len(), message.to_plain_text().len());
+        assert_eq!(message.byte_len(), 3 + 18 + 8 + 3);
+        assert_eq!(message.char_len(), 3 + 5 + 2 + 2);
+        #[cfg(feature = "unicode")]
+        assert_eq!(message.grapheme_len(), 3 + 1 + 1 + 1);
+
+        assert_eq!(Component::default().byte_len(), 0);
+        assert_eq!(Component::default().char_len(), 0);
+    }
+
+    #[test]
+    fn test_empty_and_single_element_arrays() {
+        // An empty array is written as the empty text it renders as
+        let empty = Component::Array(vec![]);
+        assert_eq!(serde_json::to_string(&empty).unwrap(), r#""""#);
+        let nested = Component::text("a").append(Component::Array(vec![]));
+        assert_eq!(
+            serde_json::to_string(&nested).unwrap(),
+            r#"{"text":"a","extra":[""]}"#
+        );
+        // and `[]` is read as empty text
+        assert_eq!(
+            serde_json::from_str::<Component>("[]").unwrap(),
+            Component::String(String::new())
+        );
+
+        // A single element array stays an array unless compacted
+        let single = Component::Array(vec![Component::text("solo")]);
+        assert_eq!(
+            serde_json::to_string(&single).unwrap(),
+            r#"[{"text":"solo"}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Component>(r#"[{"text":"solo"}]"#).unwrap(),
+            single
+        );
+        assert_eq!(single.compact(), Component::text("solo"));
+    }
+
+    #[test]
+    fn test_lines() {
+        let gold = Some(Color::Named(NamedColor::Gold));
+        let motd = Component::lines([
+            Component::text("Welcome!").color(gold.clone()),
+            Component::text("Players online: 3"),
+            Component::text("Have fun"),
+        ]);
+        assert_eq!(
+            motd.to_plain_text(),
+            "Welcome!\nPlayers online: 3\nHave fun"
+        );
+        assert_eq!(motd.to_plain_text().matches('\n').count(), 2);
+        // The first line's color doesn't leak into the others
+        assert_eq!(
+            motd.to_spans()
+                .iter()
+                .map(|span| span.style.color.clone())
+                .collect::<Vec<_>>(),
+            [gold, None, None, None, None]
+        );
+
+        assert_eq!(Component::lines([]), Component::empty());
+        assert_eq!(
+            Component::lines([Component::text("one")]).to_plain_text(),
+            "one"
+        );
+
+        let prepended = Component::text("b").prepend_newline();
+        assert_eq!(prepended.to_plain_text(), "\nb");
+        assert_eq!(
+            Component::text("a").append(prepended).to_plain_text(),
+            "a\nb"
+        );
+    }
+
+    #[test]
+    fn test_prefer_string_shorthand() {
+        let shorthand = SerializeOptions {
+            prefer_string_shorthand: true,
+        };
+        let plain = Component::text("x");
+        assert_eq!(plain.to_json_with(shorthand).unwrap(), r#""x""#);
+        assert_eq!(
+            plain.to_json_with(SerializeOptions::default()).unwrap(),
+            r#"{"text":"x"}"#
+        );
+        assert_eq!(
+            plain.to_json_with(SerializeOptions::default()).unwrap(),
+            serde_json::to_string(&plain).unwrap()
+        );
+
+        // Styled nodes and nodes with children keep the object form, and nested plain text
+        // is collapsed wherever it appears
+        let message = Component::text("a")
+            .color(Some(Color::Named(NamedColor::Red)))
+            .append(Component::text("b"))
+            .append(Component::translatable("chat.type.text").with_arg(Component::text("c")))
+            .hover_text(Component::text("tip"));
+        assert_eq!(
+            message.to_value_with(shorthand).unwrap(),
+            serde_json::json!({
+                "text": "a",
+                "color": "red",
+                "hover_event": {"action": "show_text", "value": "tip"},
+                "extra": ["b", {"translate": "chat.type.text", "with": ["c"]}]
+            })
+        );
+        assert_eq!(
+            Component::from_value(message.to_value_with(shorthand).unwrap())
+                .unwrap()
+                .to_plain_text(),
+            message.to_plain_text()
+        );
+    }
+
+    #[test]
+    fn test_is_plain() {
+        let plain = Component::Array(vec![
+            Component::text("a").append(Component::from("b")),
+            Component::text("c").content_type(Some(ContentType::Text)),
+        ]);
+        assert!(plain.is_plain());
+        assert!(Component::default().is_plain());
+
+        let red = Some(Color::Named(NamedColor::Red));
+        let styled = [
+            Component::text("a").append(Component::text("b").color(red)),
+            Component::text("a").click_run("/spawn"),
+            Component::text("a").append(Component::text("b").insertion(Some("b".into()))),
+            Component::Array(vec!["a".into(), Component::translatable("gui.done")]),
+            Component::text("a").append(Component::keybind("key.jump")),
+        ];
+        for component in styled {
+            assert!(!component.is_plain(), "{component:?}");
+        }
+    }
+
+    #[test]
+    fn test_array_hover_text_json_round_trip() {
+        let json = r#"{"text":"x","hover_event":{"action":"show_text","value":["a",{"text":"b","color":"red"}]}}"#;
+        let component: Component = serde_json::from_str(json).unwrap();
+        let Some(HoverEvent::ShowText { value }) = &component.as_object().unwrap().hover_event
+        else {
+            panic!("expected hover text");
+        };
+        assert_eq!(
+            value,
+            &Component::Array(vec![
+                "a".into(),
+                Component::text("b").color(Some(Color::Named(NamedColor::Red))),
+            ])
+        );
+
+        let serialized = serde_json::to_string(&component).unwrap();
+        assert_eq!(serialized, json);
+        assert_eq!(
+            serde_json::from_str::<Component>(&serialized).unwrap(),
+            component
+        );
+    }
+
+    #[test]
+    fn test_show_dialog_and_custom_click_events() {
+        let by_id: Component = serde_json::from_str(
+            r#"{"text":"Links","click_event":{"action":"show_dialog","dialog":"minecraft:server_links"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            by_id,
+            Component::text("Links").click_show_dialog("minecraft:server_links")
+        );
+
+        let inline = serde_json::json!({"type": "minecraft:notice", "title": "Rules"});
+        let json = format!(
+            r#"{{"text":"Rules","click_event":{{"action":"show_dialog","dialog":{inline}}}}}"#
+        );
+        let component: Component = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            component.as_object().unwrap().click_event,
+            Some(ClickEvent::ShowDialog { dialog: inline })
+        );
+        assert_eq!(serde_json::to_string(&component).unwrap(), json);
+
+        let custom = Component::text("Vote")
+            .click_custom("myplugin:vote", Some(serde_json::json!({"option": 2})));
+        let json = serde_json::to_string(&custom).unwrap();
+        assert_eq!(
+            json,
+            r#"{"text":"Vote","click_event":{"action":"custom","id":"myplugin:vote","payload":{"option":2}}}"#
+        );
+        assert_eq!(serde_json::from_str::<Component>(&json).unwrap(), custom);
+
+        let no_payload: Component = serde_json::from_str(
+            r#"{"text":"Ping","click_event":{"action":"custom","id":"myplugin:ping"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            no_payload,
+            Component::text("Ping").click_custom("myplugin:ping", None)
+        );
+
+        // Older clients don't know these actions
+        assert_eq!(
+            custom.downgrade(McVersion::V1_21_4),
+            Component::text("Vote")
+        );
+
+        // Unknown actions are kept as they are
+        let json = r#"{"text":"x","click_event":{"action":"launch_rocket","target":"moon"}}"#;
+        let unknown: Component = serde_json::from_str(json).unwrap();
+        let event = unknown.as_object().unwrap().click_event.clone().unwrap();
+        assert!(matches!(event, ClickEvent::Other(_)));
+        assert_eq!(event.action(), "launch_rocket");
+        assert_eq!(
+            serde_json::to_value(&unknown).unwrap(),
+            serde_json::from_str::<Value>(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(Component::concat([]), Component::empty());
+        assert_eq!(Component::concat(Vec::new()).to_plain_text(), "");
+
+        let red = Some(Color::Named(NamedColor::Red));
+        let parts = vec![
+            Component::text("a").color(red.clone()),
+            Component::from("b"),
+            Component::text("c").decoration(TextDecoration::Bold, Some(true)),
+        ];
+        let joined = Component::concat(parts.clone());
+        assert_eq!(joined, Component::text("").set_children(parts));
+        assert_eq!(joined.to_plain_text(), "abc");
+        // Only the first part is red
+        assert_eq!(
+            joined
+                .iter_text()
+                .map(|(_, style)| style.color)
+                .collect::<Vec<_>>(),
+            [red, None, None]
+        );
+    }
+
+    #[test]
+    fn test_click_event_command_and_url() {
+        let run = ClickEvent::RunCommand {
+            command: "/spawn".into(),
+        };
+        let suggest = ClickEvent::SuggestCommand {
+            command: "/msg Steve ".into(),
+        };
+        let url = ClickEvent::OpenUrl {
+            url: "https://minecraft.wiki".into(),
+        };
+        assert_eq!(run.command(), Some("/spawn"));
+        assert_eq!(suggest.command(), Some("/msg Steve "));
+        assert_eq!(url.command(), None);
+        assert_eq!(url.url(), Some("https://minecraft.wiki"));
+        assert_eq!(run.url(), None);
+        assert_eq!(
+            ClickEvent::CopyToClipboard {
+                value: "/spawn".into()
+            }
+            .command(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_lenient_decoration_flags() {
+        let bold = Component::text("x").decoration(TextDecoration::Bold, Some(true));
+        let not_italic = Component::text("x").decoration(TextDecoration::Italic, Some(false));
+        let cases = [
+            (r#"{"text":"x","bold":true}"#, &bold),
+            (r#"{"text":"x","bold":"true"}"#, &bold),
+            (r#"{"text":"x","bold":1}"#, &bold),
+            (r#"{"text":"x","italic":false}"#, &not_italic),
+            (r#"{"text":"x","italic":"false"}"#, &not_italic),
+            (r#"{"text":"x","italic":0}"#, &not_italic),
+        ];
+        for (json, expected) in cases {
+            let component: Component = serde_json::from_str(json).unwrap();
+            assert_eq!(&component, expected, "{json}");
+        }
+
+        let dirty: Component = serde_json::from_str(
+            r#"{"text":"x","underlined":"true","strikethrough":0,"obfuscated":null}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_string(&dirty).unwrap(),
+            r#"{"text":"x","underlined":true,"strikethrough":false}"#
+        );
+
+        for json in [
+            r#"{"text":"x","bold":"yes"}"#,
+            r#"{"text":"x","bold":2}"#,
+            r#"{"text":"x","bold":"TRUE"}"#,
+        ] {
+            assert!(serde_json::from_str::<Component>(json).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_translatable_builder() {
+        let chat = Component::translatable("chat.type.text")
+            .with_args(vec![
+                Component::text("Steve").color(Some(Color::Named(NamedColor::Yellow))),
+                Component::text("Hello!"),
+            ])
+            .fallback(Some("<%s> %s".into()));
+        assert_eq!(
+            serde_json::to_string(&chat).unwrap(),
+            r#"{"translate":"chat.type.text","fallback":"<%s> %s","with":[{"text":"Steve","color":"yellow"},{"text":"Hello!"}]}"#
+        );
+        assert_eq!(
+            chat.fallback(None),
+            Component::translatable("chat.type.text")
+                .with_arg(Component::text("Steve").color(Some(Color::Named(NamedColor::Yellow))))
+                .with_arg(Component::text("Hello!"))
+        );
+    }
+
+    #[test]
+    fn test_content_constructors() {
+        let cases = [
+            (
+                Component::score("Steve", "deaths"),
+                r#"{"score":{"name":"Steve","objective":"deaths"}}"#,
+            ),
+            (Component::selector("@a"), r#"{"selector":"@a"}"#),
+            (Component::keybind("key.jump"), r#"{"keybind":"key.jump"}"#),
+            (
+                Component::nbt("Items[0].id", NbtSource::Block, "~ ~-1 ~"),
+                r#"{"nbt":"Items[0].id","source":"block","block":"~ ~-1 ~"}"#,
+            ),
+            (
+                Component::nbt("quest.title", NbtSource::Storage, "myplugin:data"),
+                r#"{"nbt":"quest.title","source":"storage","storage":"myplugin:data"}"#,
+            ),
+        ];
+        for (component, json) in cases {
+            assert_eq!(serde_json::to_string(&component).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Component>(json).unwrap(), component);
+            assert_eq!(component.as_object().unwrap().validate_content(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_separator_builder() {
+        let players = Component::selector("@a").separator(Some(Component::text(", ")));
+        assert_eq!(
+            players.as_object().unwrap().separator.as_deref(),
+            Some(&Component::text(", "))
+        );
+        let json = serde_json::to_string(&players).unwrap();
+        assert_eq!(json, r#"{"selector":"@a","separator":{"text":", "}}"#);
+        assert_eq!(serde_json::from_str::<Component>(&json).unwrap(), players);
+
+        assert_eq!(players.separator(None), Component::selector("@a"));
+    }
+
+    #[test]
+    fn test_set_children_on_array() {
+        let list = Component::Array(vec![Component::text("a").append("x"), Component::text("b")]);
+        assert_eq!(list.get_children().len(), 2);
+        let replaced = list.set_children(vec![Component::text("c")]);
+        assert_eq!(
+            serde_json::to_value(&replaced).unwrap(),
+            serde_json::json!({"text": "a", "extra": [{"text": "c"}]})
+        );
+        assert_eq!(
+            Component::Array(vec![]).set_children(vec![Component::text("c")]),
+            Component::text("").append(Component::text("c"))
+        );
+    }
+
+    #[test]
+    fn test_add_operators() {
+        let added = Component::text("a") + "b" + Component::text("c");
+        let expected = Component::text("a")
+            .append("b")
+            .append(Component::text("c"));
+        assert_eq!(added, expected);
+
+        let mut assigned = Component::text("a");
+        assigned += "b";
+        assigned += Component::text("c");
+        assert_eq!(assigned, expected);
+    }
 }
@@ -0,0 +1,535 @@
+//! Size limits that servers and clients enforce on components.
+//!
+//! [`Component::check_limits`] validates a component against a set of [`Limits`] before it is
+//! sent, so a command generator can reject or split oversized messages up front instead of
+//! having the game refuse them.
+
+use crate::{ClickEvent, Color, Component, ComponentObject, HoverEvent, ShadowColor, UuidRepr};
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::fmt;
+
+/// Nesting depth Minecraft accepts when reading NBT, which components are stored as
+const MAX_NBT_DEPTH: usize = 512;
+
+/// Upper bounds a component has to stay within. `None` means unlimited.
+///
+/// ```
+/// use kyori_component_json::Component;
+/// use kyori_component_json::limits::{LimitViolation, Limits};
+///
+/// let long = Component::text("a".repeat(300));
+/// assert_eq!(
+///     long.check_limits(&Limits::chat()),
+///     Err(LimitViolation::TextLength { len: 300, max: 256 })
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Limits {
+    /// Maximum number of characters of the plain text, see [`Component::to_plain_text`]
+    pub max_text_len: Option<usize>,
+    /// Maximum length in bytes of the compact JSON, see [`Component::estimate_command_length`]
+    pub max_json_len: Option<usize>,
+    /// Maximum nesting depth, counting the root as depth 1
+    pub max_depth: Option<usize>,
+    /// Maximum number of components in the tree, see [`Component::nodes`]
+    pub max_nodes: Option<usize>,
+}
+
+impl Limits {
+    /// No limits at all
+    pub const fn none() -> Self {
+        Limits {
+            max_text_len: None,
+            max_json_len: None,
+            max_depth: None,
+            max_nodes: None,
+        }
+    }
+
+    /// A single line of a sign: at most 256 characters.
+    ///
+    /// The game really limits sign lines by their rendered width, which
+    /// [`Component::to_sign_lines`] takes care of; this is a sanity bound on top of that.
+    pub const fn sign_line() -> Self {
+        Limits {
+            max_text_len: Some(256),
+            max_depth: Some(MAX_NBT_DEPTH),
+            ..Limits::none()
+        }
+    }
+
+    /// A page of a written book, whose JSON is stored as a string of at most 32767 characters
+    pub const fn book_page() -> Self {
+        Limits {
+            max_json_len: Some(32767),
+            max_depth: Some(MAX_NBT_DEPTH),
+            ..Limits::none()
+        }
+    }
+
+    /// A chat message typed by a player, limited to 256 characters
+    pub const fn chat() -> Self {
+        Limits {
+            max_text_len: Some(256),
+            ..Limits::none()
+        }
+    }
+
+    /// The component argument of a `/tellraw` run from a command block or function, where the
+    /// whole command is limited to 32500 characters
+    pub const fn tellraw() -> Self {
+        Limits {
+            max_json_len: Some(32500 - "tellraw @a ".len()),
+            max_depth: Some(MAX_NBT_DEPTH),
+            ..Limits::none()
+        }
+    }
+}
+
+/// The first limit a component exceeded, reported by [`Component::check_limits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitViolation {
+    /// The plain text has more characters than allowed
+    TextLength {
+        /// Characters in the plain text
+        len: usize,
+        /// The limit
+        max: usize,
+    },
+    /// The serialized JSON is longer than allowed
+    JsonLength {
+        /// Bytes of compact JSON
+        len: usize,
+        /// The limit
+        max: usize,
+    },
+    /// Components are nested deeper than allowed
+    Depth {
+        /// Nesting depth of the component
+        depth: usize,
+        /// The limit
+        max: usize,
+    },
+    /// The tree has more components than allowed
+    NodeCount {
+        /// Number of components in the tree
+        count: usize,
+        /// The limit
+        max: usize,
+    },
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitViolation::TextLength { len, max } => {
+                write!(f, "text is {len} characters long, the limit is {max}")
+            }
+            LimitViolation::JsonLength { len, max } => {
+                write!(f, "JSON is {len} bytes long, the limit is {max}")
+            }
+            LimitViolation::Depth { depth, max } => {
+                write!(
+                    f,
+                    "component is nested {depth} levels deep, the limit is {max}"
+                )
+            }
+            LimitViolation::NodeCount { count, max } => {
+                write!(f, "component has {count} nodes, the limit is {max}")
+            }
+        }
+    }
+}
+
+impl Error for LimitViolation {}
+
+impl Component {
+    /// Checks this component against `limits`.
+    ///
+    /// Node count and depth cover everything nested in the component, including hover text
+    /// and translation arguments. The cheap structural checks run first.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`LimitViolation`] found, checking the node count, depth, text length
+    /// and JSON length in that order.
+    pub fn check_limits(&self, limits: &Limits) -> Result<(), LimitViolation> {
+        if let Some(max) = limits.max_nodes {
+            let count = self.nodes().count();
+            if count > max {
+                return Err(LimitViolation::NodeCount { count, max });
+            }
+        }
+        if let Some(max) = limits.max_depth {
+            let depth = self.depth();
+            if depth > max {
+                return Err(LimitViolation::Depth { depth, max });
+            }
+        }
+        if let Some(max) = limits.max_text_len {
+            let len = self.to_plain_text().chars().count();
+            if len > max {
+                return Err(LimitViolation::TextLength { len, max });
+            }
+        }
+        // Only serialize when the cheap upper bound doesn't already rule out a violation
+        if let Some(max) = limits.max_json_len
+            && self.estimate_json_size() > max
+        {
+            let len = self.estimate_command_length();
+            if len > max {
+                return Err(LimitViolation::JsonLength { len, max });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns an upper bound on the length in bytes of this component serialized as compact
+    /// JSON, computed by walking the tree rather than serializing it.
+    ///
+    /// The estimate is never below [`Component::estimate_command_length`] and is usually a
+    /// few bytes above it, so it can cheaply show that a component fits within a limit; only
+    /// when it doesn't is the exact length worth computing.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let message = Component::text("Hello").append(Component::text("world"));
+    /// assert!(message.estimate_json_size() >= message.estimate_command_length());
+    /// ```
+    pub fn estimate_json_size(&self) -> usize {
+        match self {
+            Component::String(s) => string_size(s),
+            Component::Array(children) => array_size(children),
+            Component::Object(obj) => object_size(obj),
+        }
+    }
+
+    /// Nesting depth of this component, counting itself as 1
+    pub(crate) fn depth(&self) -> usize {
+        let children: Box<dyn Iterator<Item = &Component>> = match self {
+            Component::String(_) => return 1,
+            Component::Array(children) => Box::new(children.iter()),
+            Component::Object(obj) => Box::new(obj.nested()),
+        };
+        1 + children.map(Component::depth).max().unwrap_or(0)
+    }
+}
+
+/// Longest serialized form of a named color, `"light_purple"`
+const NAMED_COLOR_SIZE: usize = 14;
+/// Longest serialized content type, `"translatable"`
+const CONTENT_TYPE_SIZE: usize = 14;
+/// Longest serialized NBT source, `"storage"`
+const NBT_SOURCE_SIZE: usize = 9;
+/// Longest `i32`, `-2147483648`
+const I32_SIZE: usize = 11;
+/// Upper bound for any JSON number, integer or float
+const NUMBER_SIZE: usize = 24;
+
+/// Size of a `"key":value,` entry of an object, counting a trailing comma for simplicity
+fn field_size(key: &str, value: usize) -> usize {
+    key.len() + 4 + value
+}
+
+/// Exact size of `s` as a JSON string, quotes and escapes included
+fn string_size(s: &str) -> usize {
+    2 + s
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' | '\n' | '\r' | '\t' | '\u{8}' | '\u{c}' => 2,
+            c if c < ' ' => 6,
+            c => c.len_utf8(),
+        })
+        .sum::<usize>()
+}
+
+fn array_size(children: &[Component]) -> usize {
+    2 + children
+        .iter()
+        .map(|child| child.estimate_json_size() + 1)
+        .sum::<usize>()
+}
+
+fn object_size(obj: &ComponentObject) -> usize {
+    let strings = [
+        ("text", &obj.text),
+        ("translate", &obj.translate),
+        ("fallback", &obj.fallback),
+        ("selector", &obj.selector),
+        ("keybind", &obj.keybind),
+        ("nbt", &obj.nbt),
+        ("block", &obj.block),
+        ("entity", &obj.entity),
+        ("storage", &obj.storage),
+        ("font", &obj.font),
+        ("insertion", &obj.insertion),
+    ];
+    let bools = [
+        ("interpret", obj.interpret),
+        ("bold", obj.bold),
+        ("italic", obj.italic),
+        ("underlined", obj.underlined),
+        ("strikethrough", obj.strikethrough),
+        ("obfuscated", obj.obfuscated),
+    ];
+
+    let mut size = 2;
+    for (key, value) in strings {
+        if let Some(value) = value {
+            size += field_size(key, string_size(value));
+        }
+    }
+    for (key, value) in bools {
+        if value.is_some() {
+            size += field_size(key, "false".len());
+        }
+    }
+    if obj.content_type.is_some() {
+        size += field_size("type", CONTENT_TYPE_SIZE);
+    }
+    if obj.source.is_some() {
+        size += field_size("source", NBT_SOURCE_SIZE);
+    }
+    if let Some(with) = &obj.with {
+        size += field_size("with", array_size(with));
+    }
+    if let Some(score) = &obj.score {
+        let mut score_size = 2
+            + field_size("name", string_size(&score.name))
+            + field_size("objective", string_size(&score.objective));
+        if let Some(value) = &score.value {
+            score_size += field_size("value", string_size(value));
+        }
+        size += field_size("score", score_size);
+    }
+    if let Some(separator) = &obj.separator {
+        size += field_size("separator", separator.estimate_json_size());
+    }
+    if let Some(extra) = &obj.extra {
+        size += field_size("extra", array_size(extra));
+    }
+    if let Some(color) = &obj.color {
+        let color_size = match color {
+            Color::Named(_) => NAMED_COLOR_SIZE,
+            Color::Hex(hex) => string_size(hex),
+        };
+        size += field_size("color", color_size);
+    }
+    if let Some(shadow) = &obj.shadow_color {
+        let shadow_size = match shadow {
+            ShadowColor::Int(_) => I32_SIZE,
+            ShadowColor::Floats(channels) => 2 + channels.len() * (NUMBER_SIZE + 1),
+        };
+        size += field_size("shadow_color", shadow_size);
+    }
+    if let Some(click) = &obj.click_event {
+        size += field_size("click_event", click_event_size(click));
+    }
+    if let Some(hover) = &obj.hover_event {
+        size += field_size("hover_event", hover_event_size(hover));
+    }
+    size
+}
+
+fn click_event_size(event: &ClickEvent) -> usize {
+    let contents = match event {
+        ClickEvent::OpenUrl { url } => field_size("url", string_size(url)),
+        ClickEvent::OpenFile { path } => field_size("path", string_size(path)),
+        ClickEvent::RunCommand { command } | ClickEvent::SuggestCommand { command } => {
+            field_size("command", string_size(command))
+        }
+        ClickEvent::ChangePage { page: _ } => field_size("page", I32_SIZE),
+        ClickEvent::CopyToClipboard { value } => field_size("value", string_size(value)),
+        ClickEvent::ShowDialog { dialog } => field_size("dialog", value_size(dialog)),
+        ClickEvent::Custom { id, payload } => {
+            field_size("id", string_size(id))
+                + payload
+                    .as_ref()
+                    .map_or(0, |payload| field_size("payload", value_size(payload)))
+        }
+        // The raw event already holds its `action`
+        ClickEvent::Other(event) => return map_size(event),
+    };
+    2 + field_size("action", string_size(event.action())) + contents
+}
+
+fn hover_event_size(event: &HoverEvent) -> usize {
+    let contents = match event {
+        HoverEvent::ShowText { value } => field_size("value", value.estimate_json_size()),
+        HoverEvent::ShowItem {
+            id,
+            count,
+            components,
+        } => {
+            let mut size = field_size("id", string_size(id));
+            if count.is_some() {
+                size += field_size("count", I32_SIZE);
+            }
+            if let Some(components) = components {
+                size += field_size("components", value_size(components));
+            }
+            size
+        }
+        HoverEvent::ShowEntity { name, id, uuid } => {
+            let uuid_size = match uuid {
+                UuidRepr::String(uuid) => string_size(uuid),
+                UuidRepr::IntArray(ints) => 2 + ints.len() * (I32_SIZE + 1),
+            };
+            let mut size = field_size("id", string_size(id)) + field_size("uuid", uuid_size);
+            if let Some(name) = name {
+                size += field_size("name", name.estimate_json_size());
+            }
+            size
+        }
+    };
+    2 + field_size("action", string_size(event.action())) + contents
+}
+
+fn value_size(value: &Value) -> usize {
+    match value {
+        Value::Null => "null".len(),
+        Value::Bool(_) => "false".len(),
+        Value::Number(_) => NUMBER_SIZE,
+        Value::String(s) => string_size(s),
+        Value::Array(values) => 2 + values.iter().map(|v| value_size(v) + 1).sum::<usize>(),
+        Value::Object(map) => map_size(map),
+    }
+}
+
+fn map_size(map: &Map<String, Value>) -> usize {
+    2 + map
+        .iter()
+        .map(|(key, value)| string_size(key) + value_size(value) + 2)
+        .sum::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(depth: usize) -> Component {
+        (1..depth).fold(Component::text("x"), |inner, _| {
+            Component::text("").append(inner)
+        })
+    }
+
+    #[test]
+    fn test_presets_accept_small_components() {
+        let small = Component::text("Hello").append(Component::text("world"));
+        for limits in [
+            Limits::sign_line(),
+            Limits::book_page(),
+            Limits::chat(),
+            Limits::tellraw(),
+            Limits::none(),
+        ] {
+            assert_eq!(small.check_limits(&limits), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_presets_reject_oversized_components() {
+        let long_text = Component::text("a".repeat(257));
+        assert_eq!(
+            long_text.check_limits(&Limits::sign_line()),
+            Err(LimitViolation::TextLength { len: 257, max: 256 })
+        );
+        assert!(matches!(
+            long_text.check_limits(&Limits::chat()),
+            Err(LimitViolation::TextLength { .. })
+        ));
+
+        let huge = Component::text("a".repeat(33_000));
+        assert!(matches!(
+            huge.check_limits(&Limits::book_page()),
+            Err(LimitViolation::JsonLength { max: 32767, .. })
+        ));
+        assert!(matches!(
+            huge.check_limits(&Limits::tellraw()),
+            Err(LimitViolation::JsonLength { .. })
+        ));
+
+        assert_eq!(
+            nested(600).check_limits(&Limits::tellraw()),
+            Err(LimitViolation::Depth {
+                depth: 600,
+                max: 512
+            })
+        );
+    }
+
+    #[test]
+    fn test_node_count_and_depth_include_hover_text() {
+        let message = Component::text("a").hover_text(Component::text("b").append("c"));
+        assert_eq!(message.depth(), 3);
+
+        let limits = Limits {
+            max_nodes: Some(2),
+            ..Limits::none()
+        };
+        assert_eq!(
+            message.check_limits(&limits),
+            Err(LimitViolation::NodeCount { count: 3, max: 2 })
+        );
+    }
+
+    #[test]
+    fn test_estimate_is_an_upper_bound() {
+        use crate::{
+            ClickEvent, Color, ComponentObject, HoverEvent, NamedColor, ScoreContent, ShadowColor,
+            TextDecoration, UuidRepr,
+        };
+
+        let score = ComponentObject {
+            score: Some(ScoreContent::new("@s", "kills").value("7")),
+            separator: Some(Box::new(Component::from(", "))),
+            ..Default::default()
+        };
+        let components = [
+            Component::from("plain"),
+            Component::text("quote \" back\\slash \n tab\t bell\u{7} \u{e9}\u{1F600}"),
+            Component::Array(vec![Component::from("a"), Component::text("b"), nested(4)]),
+            Component::text("styled")
+                .color(Some(Color::Named(NamedColor::LightPurple)))
+                .decoration(TextDecoration::Bold, Some(false))
+                .font(Some("minecraft:uniform".into()))
+                .insertion(Some("ins".into()))
+                .click_event(Some(ClickEvent::ChangePage {
+                    page: -2_147_483_648,
+                }))
+                .hover_event(Some(HoverEvent::ShowItem {
+                    id: "minecraft:diamond".into(),
+                    count: Some(64),
+                    components: Some(serde_json::json!({
+                        "minecraft:lore": ["x", {"n": 1.0e-7, "b": true, "z": null}],
+                    })),
+                })),
+            Component::text("hex")
+                .color(Some(Color::Hex("#A1B2C3".into())))
+                .map_object(|mut obj| {
+                    obj.shadow_color = Some(ShadowColor::Floats([1.0 / 3.0, 0.1, 0.0, 1.0]));
+                    obj
+                })
+                .hover_event(Some(HoverEvent::ShowEntity {
+                    name: Some(Component::text("Steve")),
+                    id: "minecraft:player".into(),
+                    uuid: UuidRepr::IntArray([i32::MIN, -1, 0, i32::MAX]),
+                })),
+            Component::Object(Box::new(score)),
+        ];
+        for component in components {
+            let exact = component.estimate_command_length();
+            let estimate = component.estimate_json_size();
+            assert!(
+                estimate >= exact,
+                "estimate {estimate} < exact {exact} for {component:?}"
+            );
+        }
+
+        // Plain strings have no overhead to over-count
+        let text = Component::from("a\"b");
+        assert_eq!(text.estimate_json_size(), text.estimate_command_length());
+    }
+}
@@ -4,6 +4,123 @@
 //! Kyori `Component` objects. It simplifies the process of creating complex components
 //! by allowing properties to be specified in a more readable, attribute-like syntax.
 
+/// Compile-time helpers used by `component!` to check its arguments.
+///
+/// They're `const fn`s so that the macro can evaluate them in a `const` block, turning a
+/// typo like `color: yelow` into a compile error instead of a panic at runtime.
+#[doc(hidden)]
+pub mod __private {
+    use crate::{NamedColor, TextDecoration, colors::NAME_TO_NAMED_COLOR};
+
+    /// Compares two ASCII strings ignoring case
+    const fn eq_ignore_ascii_case(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if !a[i].eq_ignore_ascii_case(&b[i]) {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Looks up a named color by name, ignoring case, like `NamedColor::from_str`
+    pub const fn named_color(name: &str) -> Option<NamedColor> {
+        let mut i = 0;
+        while i < NAME_TO_NAMED_COLOR.len() {
+            let (candidate, color) = NAME_TO_NAMED_COLOR[i];
+            if eq_ignore_ascii_case(candidate, name) {
+                return Some(color);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Looks up a decoration by name, ignoring case, like `TextDecoration::from_str`
+    pub const fn decoration(name: &str) -> Option<TextDecoration> {
+        const DECORATIONS: [(&str, TextDecoration); 5] = [
+            ("bold", TextDecoration::Bold),
+            ("italic", TextDecoration::Italic),
+            ("underlined", TextDecoration::Underlined),
+            ("strikethrough", TextDecoration::Strikethrough),
+            ("obfuscated", TextDecoration::Obfuscated),
+        ];
+        let mut i = 0;
+        while i < DECORATIONS.len() {
+            if eq_ignore_ascii_case(DECORATIONS[i].0, name) {
+                return Some(DECORATIONS[i].1);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Checks that `hex` is `#RRGGBB`, ignoring the spaces `stringify!` may insert after `#`
+    pub const fn is_hex_color(hex: &str) -> bool {
+        let hex = hex.as_bytes();
+        let mut digits = 0;
+        let mut i = 0;
+        while i < hex.len() {
+            match hex[i] {
+                b' ' => {}
+                b'#' if digits == 0 => {}
+                c if c.is_ascii_hexdigit() => digits += 1,
+                _ => return false,
+            }
+            i += 1;
+        }
+        digits == 6 && !hex.is_empty() && hex[0] == b'#'
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+/// Helper macro to look up a named color at compile time, failing to compile on an unknown
+/// name.
+macro_rules! __named_color {
+    ($color:ident) => {{
+        // A named constant, unlike an inline `const` block, is also evaluated by `cargo check`
+        const COLOR: $crate::NamedColor = match $crate::__private::named_color(stringify!($color)) {
+            Some(color) => color,
+            None => panic!(concat!("unknown color `", stringify!($color), "`")),
+        };
+        COLOR
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+/// Helper macro to look up a text decoration at compile time, failing to compile on an
+/// unknown name.
+macro_rules! __decoration {
+    ($deco:ident) => {{
+        const DECORATION: $crate::TextDecoration =
+            match $crate::__private::decoration(stringify!($deco)) {
+                Some(deco) => deco,
+                None => panic!(concat!("unknown decoration `", stringify!($deco), "`")),
+            };
+        DECORATION
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+/// Helper macro to build a hex color, failing to compile unless it is `#RRGGBB`.
+macro_rules! __hex_color {
+    (#$hex:literal) => {{
+        const _: () = assert!(
+            $crate::__private::is_hex_color(stringify!(#$hex)),
+            concat!("invalid hex color `#", stringify!($hex), "`, expected #RRGGBB")
+        );
+        $crate::Color::Hex(stringify!(#$hex).replace(" ", ""))
+    }};
+}
+
 #[doc(hidden)]
 #[macro_export]
 /// Helper macro to convert snake_case identifiers to the corresponding `ClickEvent` enum variants.
@@ -15,6 +132,8 @@ macro_rules! __click_event_from_snake {
     (suggest_command, { $($body:tt)* }) => { $crate::ClickEvent::SuggestCommand { $($body)* } };
     (change_page, { $($body:tt)* }) => { $crate::ClickEvent::ChangePage { $($body)* } };
     (copy_to_clipboard, { $($body:tt)* }) => { $crate::ClickEvent::CopyToClipboard { $($body)* } };
+    (show_dialog, { $($body:tt)* }) => { $crate::ClickEvent::ShowDialog { $($body)* } };
+    (custom, { $($body:tt)* }) => { $crate::ClickEvent::Custom { $($body)* } };
 }
 
 #[doc(hidden)]
@@ -36,13 +155,30 @@ macro_rules! __hover_event_from_snake {
 ///    and then applies various properties to it, such as color, decorations, events,
 ///    and appended components.
 ///
+/// `translate: "key"` and `keybind: "key"` can be used in place of `text: "..."` to create
+/// translatable and keybind components, in both forms.
+///
+/// Besides `property: value` pairs, the properties can include `append_newline` and
+/// `append_space`, which take no value.
+///
+/// Color names, decoration names and hex colors are checked at compile time, so a typo
+/// such as `color: yelow` fails to build instead of panicking when the code runs:
+///
+/// ```compile_fail
+/// use kyori_component_json::component;
+///
+/// let component = component!(text: "Hello", { color: yelow });
+/// ```
+///
 /// The macro uses an internal "muncher" pattern (`@munch` rules) to iteratively process
 /// the provided properties. This allows for a flexible order of properties.
 ///
 /// # Examples
 ///
 /// ```
-/// use kyori_component_json::{component, ClickEvent, Color, Component, NamedColor, TextDecoration};
+/// use kyori_component_json::{
+///     component, ClickEvent, Color, Component, NamedColor, ShadowColor, TextDecoration,
+/// };
 ///
 /// let component = component!(text: "Hello, ", {
 ///     color: yellow,
@@ -58,6 +194,13 @@ macro_rules! __hover_event_from_snake {
 /// let component2 = component!(text: "hello world", {
 ///    color: #037429,
 /// });
+///
+/// let jump = component!(text: "Press ", {
+///     shadow_color: ShadowColor::from_rgba([0, 0, 0, 255]),
+///     append: (component!(keybind: "key.jump", { color: gold })),
+///     append_space,
+///     append: (component!(translate: "options.jump")),
+/// });
 /// ```
 macro_rules! component {
     // Base case: Creates a simple text component without additional properties.
@@ -76,6 +219,26 @@ macro_rules! component {
         }
     };
 
+    // Translatable and keybind components, with and without properties
+    (translate: $key:expr) => {
+        $crate::Component::translatable($key)
+    };
+    (translate: $key:expr, { $($body:tt)* }) => {
+        {
+            let component = $crate::Component::translatable($key);
+            component!(@munch component, $($body)*)
+        }
+    };
+    (keybind: $key:expr) => {
+        $crate::Component::keybind($key)
+    };
+    (keybind: $key:expr, { $($body:tt)* }) => {
+        {
+            let component = $crate::Component::keybind($key);
+            component!(@munch component, $($body)*)
+        }
+    };
+
     // --- Muncher Rules (@munch) ---
     // The muncher pattern works by repeatedly matching and consuming one property
     // at a time, modifying the `comp` (Component) variable, and then recursively
@@ -89,33 +252,33 @@ macro_rules! component {
     // Parses the color identifier, applies it to the component, and continues munching.
     (@munch $comp:ident, color: $color:ident, $($rest:tt)*) => {
         {
-            let comp = $comp.color(Some($crate::Color::Named(stringify!($color).parse().unwrap())));
+            let comp = $comp.color(Some($crate::Color::Named($crate::__named_color!($color))));
             component!(@munch comp, $($rest)*)
         }
     };
     // Variant for named colors when it's the last property.
     (@munch $comp:ident, color: $color:ident) => {
-        $comp.color(Some($crate::Color::Named(stringify!($color).parse().unwrap())))
+        $comp.color(Some($crate::Color::Named($crate::__named_color!($color))))
     };
 
     // Rule for hex colors (e.g., `color: #FFFFFF`):
     // Parses the hex code, applies it, and continues munching.
     (@munch $comp:ident, color: #$hex:literal, $($rest:tt)*) => {
         {
-            let comp = $comp.color(Some(stringify!(#$hex).replace(" ", "").parse().unwrap()));
+            let comp = $comp.color(Some($crate::__hex_color!(#$hex)));
             component!(@munch comp, $($rest)*)
         }
     };
     // Variant for hex colors when it's the last property.
     (@munch $comp:ident, color: #$hex:literal) => {
-        $comp.color(Some(stringify!(#$hex).replace(" ", "").parse().unwrap()))
+        $comp.color(Some($crate::__hex_color!(#$hex)))
     };
 
     // Rule for text decorations (e.g., `decoration: bold & true`):
     // Parses the decoration and its state, applies it, and continues munching.
     (@munch $comp:ident, decoration: $deco:ident & $state:expr, $($rest:tt)*) => {
         {
-            let deco = stringify!($deco).parse().unwrap();
+            let deco = $crate::__decoration!($deco);
             let comp = $comp.decoration(deco, Some($state));
             component!(@munch comp, $($rest)*)
         }
@@ -123,7 +286,7 @@ macro_rules! component {
     // Variant for decorations when it's the last property.
     (@munch $comp:ident, decoration: $deco:ident & $state:expr) => {
         {
-            let deco = stringify!($deco).parse().unwrap();
+            let deco = $crate::__decoration!($deco);
             $comp.decoration(deco, Some($state))
         }
     };
@@ -154,6 +317,40 @@ macro_rules! component {
         $comp.insertion(Some($value.to_string()))
     };
 
+    // Rule for shadow colors (e.g., `shadow_color: ShadowColor::Int(-16777216)`):
+    // Applies the shadow color and continues munching.
+    (@munch $comp:ident, shadow_color: $value:expr, $($rest:tt)*) => {
+        {
+            let comp = $comp.shadow_color(Some($value));
+            component!(@munch comp, $($rest)*)
+        }
+    };
+    // Variant for shadow colors when it's the last property.
+    (@munch $comp:ident, shadow_color: $value:expr) => {
+        $comp.shadow_color(Some($value))
+    };
+
+    // Rules for `append_newline` and `append_space`, which take no value:
+    // Appends the character and continues munching.
+    (@munch $comp:ident, append_newline, $($rest:tt)*) => {
+        {
+            let comp = $comp.append_newline();
+            component!(@munch comp, $($rest)*)
+        }
+    };
+    (@munch $comp:ident, append_newline) => {
+        $comp.append_newline()
+    };
+    (@munch $comp:ident, append_space, $($rest:tt)*) => {
+        {
+            let comp = $comp.append_space();
+            component!(@munch comp, $($rest)*)
+        }
+    };
+    (@munch $comp:ident, append_space) => {
+        $comp.append_space()
+    };
+
     // Rule for click events (e.g., `click_event: run_command { command: "..." }`):
     // Uses the `__click_event_from_snake!` helper to construct the `ClickEvent`,
     // applies it, and continues munching.
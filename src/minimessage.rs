@@ -2,38 +2,189 @@
 //!
 //! Implements the [`ComponentParser`] and [`ComponentSerializer`] traits
 //! for the MiniMessage text format.
+//!
+//! # Escaping
+//!
+//! A backslash makes the next `<` literal, so `\<red>` is the text `<red>` rather than a tag,
+//! and `\\` is a single backslash. When [`MiniMessageConfig::parse_legacy_colors`] is on,
+//! `\&` and `\§` keep a legacy marker from starting a formatting code. A backslash before any
+//! other character is kept as is. The serializer escapes exactly these characters, so its
+//! output parses back to the same text.
+//!
+//! ```
+//! use kyori_component_json::Component;
+//! use kyori_component_json::minimessage::MiniMessage;
+//!
+//! let mm = MiniMessage::new();
+//! assert_eq!(mm.parse(r"\<red>not red").unwrap(), Component::text("<red>not red"));
+//! assert_eq!(mm.serialize(&Component::text("<red>")).unwrap(), r"\<red>");
+//! ```
 
+use crate::colors;
 use crate::parsing::{ComponentParser, ComponentSerializer};
 use crate::{
-    ClickEvent, Color, Component, ComponentObject, HoverEvent, NamedColor, Style, TextDecoration,
+    ClickEvent, Color, Component, ComponentObject, EMPTY_STYLE, HoverEvent, NamedColor, NbtSource,
+    ScoreContent, ShadowColor, Style, TextDecoration, UuidRepr,
 };
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
+use std::ops::Range;
 
 /// Represents errors that can occur during MiniMessage parsing/serialization.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MiniMessageError(String);
+pub struct MiniMessageError {
+    message: String,
+    position: Option<usize>,
+}
+
+impl MiniMessageError {
+    fn new(message: impl Into<String>) -> Self {
+        MiniMessageError {
+            message: message.into(),
+            position: None,
+        }
+    }
+
+    /// Sets the byte offset the error refers to
+    fn at(mut self, position: usize) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Describes what went wrong
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Byte offset in the parsed input of the tag or text that caused the error, or `None`
+    /// for serialization errors
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+}
 
 impl fmt::Display for MiniMessageError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "MiniMessage error: {}", self.0)
+        write!(f, "MiniMessage error: {}", self.message)
     }
 }
 
 impl Error for MiniMessageError {}
 
+/// How the parser treats tags it does not recognize.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UnknownTagPolicy {
+    /// Fail parsing with a [`MiniMessageError`]
+    Error,
+    /// Keep the tag as literal text (e.g. `<unknown>` stays `<unknown>`)
+    #[default]
+    AsText,
+    /// Drop the tag along with everything up to its matching closing tag.
+    ///
+    /// If no closing tag is found, only the tag itself is removed.
+    Strip,
+}
+
 /// Configuration for MiniMessage parsing/serialization.
-#[derive(Debug, Clone, Default, PartialEq, Eq, Copy, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct MiniMessageConfig {
     /// Whether to use strict parsing (requires proper tag closing)
     pub strict: bool,
-    /// Whether to parse legacy color codes (e.g., &6 for gold)
+    /// Whether to parse legacy color codes (e.g., &6 for gold). Serialization then escapes
+    /// `&` and `§` in text as `\&` and `\§`.
     pub parse_legacy_colors: bool,
+    /// What to do with tags that are not recognized
+    pub unknown_tags: UnknownTagPolicy,
+    /// Whether serialization writes line breaks as `<newline>` tags instead of raw `\n`
+    /// characters, keeping the output on a single line. Parsing accepts both either way.
+    pub newline_as_tag: bool,
+    /// Custom tags standing for a built-in tag with its arguments, written without the angle
+    /// brackets, e.g. `primary` → `gold` or `accent` → `color:#FFAA00`.
+    ///
+    /// Aliases are resolved before anything else, so one named like a built-in tag replaces
+    /// it. The target must be a built-in tag: aliases don't expand recursively. Arguments
+    /// given to the alias are appended to the target's, and `</primary>` closes `<primary>`.
+    /// Names are matched in lowercase, like all tag names.
+    pub tag_aliases: BTreeMap<String, String>,
+}
+
+impl MiniMessageConfig {
+    /// Starts building a configuration from the defaults.
+    ///
+    /// ```
+    /// use kyori_component_json::minimessage::{MiniMessage, MiniMessageConfig};
+    ///
+    /// let mm = MiniMessage::with_config(
+    ///     MiniMessageConfig::builder()
+    ///         .strict(true)
+    ///         .tag_alias("primary", "gold")
+    ///         .build(),
+    /// );
+    /// assert!(mm.parse("<primary>Hi</primary>").is_ok());
+    /// ```
+    pub fn builder() -> MiniMessageConfigBuilder {
+        MiniMessageConfigBuilder::default()
+    }
+}
+
+/// Builder for a [`MiniMessageConfig`], created by [`MiniMessageConfig::builder`].
+///
+/// Every setter corresponds to a field of the configuration; [`build`](Self::build) returns
+/// the finished configuration.
+#[derive(Debug, Clone, Default)]
+pub struct MiniMessageConfigBuilder {
+    config: MiniMessageConfig,
+}
+
+impl MiniMessageConfigBuilder {
+    /// Sets [`MiniMessageConfig::strict`]
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.config.strict = strict;
+        self
+    }
+
+    /// Sets [`MiniMessageConfig::parse_legacy_colors`]
+    #[must_use]
+    pub fn parse_legacy_colors(mut self, parse_legacy_colors: bool) -> Self {
+        self.config.parse_legacy_colors = parse_legacy_colors;
+        self
+    }
+
+    /// Sets [`MiniMessageConfig::unknown_tags`]
+    #[must_use]
+    pub fn unknown_tags(mut self, policy: UnknownTagPolicy) -> Self {
+        self.config.unknown_tags = policy;
+        self
+    }
+
+    /// Sets [`MiniMessageConfig::newline_as_tag`]
+    #[must_use]
+    pub fn newline_as_tag(mut self, newline_as_tag: bool) -> Self {
+        self.config.newline_as_tag = newline_as_tag;
+        self
+    }
+
+    /// Adds an entry to [`MiniMessageConfig::tag_aliases`], replacing any alias with the same
+    /// name. The name is lowercased, since tag names are matched in lowercase.
+    #[must_use]
+    pub fn tag_alias(mut self, name: impl AsRef<str>, target: impl Into<String>) -> Self {
+        self.config
+            .tag_aliases
+            .insert(name.as_ref().to_lowercase(), target.into());
+        self
+    }
+
+    /// Finishes the configuration
+    pub fn build(self) -> MiniMessageConfig {
+        self.config
+    }
 }
 
 /// MiniMessage parser and serializer implementation.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct MiniMessage {
     config: MiniMessageConfig,
 }
@@ -45,6 +196,9 @@ impl MiniMessage {
     }
 
     /// Creates a new MiniMessage instance with custom configuration.
+    ///
+    /// The configuration can be written as a struct literal or built with
+    /// [`MiniMessageConfig::builder`].
     pub fn with_config(config: MiniMessageConfig) -> Self {
         MiniMessage { config }
     }
@@ -54,6 +208,262 @@ impl MiniMessage {
         let mut parser = Parser::new(input.as_ref(), &self.config);
         parser.parse()
     }
+
+    /// Parses input like [`parse`](Self::parse), but keeps going past errors and returns
+    /// them alongside the result, e.g. for an editor showing a preview while highlighting
+    /// mistakes.
+    ///
+    /// A component is always returned. Each tag that causes an error is kept as literal
+    /// text, and the error records where it starts (see [`MiniMessageError::position`]).
+    /// Which problems are errors depends on the configuration as usual, e.g. unknown tags
+    /// only with [`UnknownTagPolicy::Error`].
+    ///
+    /// ```
+    /// use kyori_component_json::minimessage::{MiniMessage, MiniMessageConfig};
+    ///
+    /// let mm = MiniMessage::with_config(MiniMessageConfig::builder().strict(true).build());
+    /// let (component, errors) = mm.parse_recovering("<red>hi</blue>!");
+    /// assert_eq!(component.to_plain_text(), "hi</blue>!");
+    /// assert_eq!(errors[0].position(), Some(7));
+    /// ```
+    pub fn parse_recovering(&self, input: impl AsRef<str>) -> (Component, Vec<MiniMessageError>) {
+        let mut parser = Parser::new(input.as_ref(), &self.config);
+        parser.diagnostics = Some(Vec::new());
+        // Errors are all recorded in recovery mode, so parsing itself can't fail
+        let component = parser.parse().unwrap_or_default();
+        (component, parser.diagnostics.unwrap_or_default())
+    }
+
+    /// Serializes a component using instance configuration
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    /// use kyori_component_json::minimessage::{MiniMessage, MiniMessageConfig};
+    ///
+    /// let mm = MiniMessage::with_config(MiniMessageConfig {
+    ///     newline_as_tag: true,
+    ///     ..Default::default()
+    /// });
+    /// let text = Component::text("first\nsecond");
+    /// assert_eq!(mm.serialize(&text).unwrap(), "first<newline>second");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component uses something MiniMessage has no tag for: an NBT
+    /// component without a source, or a click event other than the six classic actions.
+    pub fn serialize(&self, component: &Component) -> Result<String, MiniMessageError> {
+        Serializer::new(&self.config).serialize(component)
+    }
+
+    /// Parses text using legacy `§`/`&` formatting codes by converting it with
+    /// [`legacy_to_minimessage`] and parsing the result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the converted text is not valid MiniMessage, which can happen when
+    /// the input already contains MiniMessage-like tags.
+    pub fn from_legacy(input: &str) -> Result<Component, MiniMessageError> {
+        MiniMessage::new().parse(legacy_to_minimessage(input))
+    }
+}
+
+/// A formatting code from the legacy `§`/`&` format
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LegacyCode {
+    Color(Color),
+    Decoration(TextDecoration),
+    Reset,
+}
+
+/// A piece of legacy-formatted text: either literal text or a formatting code
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LegacySegment<'a> {
+    Text(&'a str),
+    Code(LegacyCode),
+}
+
+/// Splits text on legacy formatting codes (`§c`, `&l`, `&r`, ...), including the `&#RRGGBB`
+/// and `§x§R§R§G§G§B§B` hex forms. Markers not followed by a valid code stay in the text.
+///
+/// With `escapes`, a marker preceded by a backslash is skipped; the backslash is left in the
+/// text for [`unescape_text`] to remove.
+fn legacy_segments(input: &str, escapes: bool) -> Vec<LegacySegment<'_>> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut rest = input;
+    let markers: &[char] = if escapes {
+        &['§', '&', '\\']
+    } else {
+        &['§', '&']
+    };
+    while let Some(offset) = rest.find(markers) {
+        let marker_at = input.len() - rest.len() + offset;
+        let marker = &rest[offset..];
+        let after = &marker[marker.chars().next().map_or(0, char::len_utf8)..];
+        if marker.starts_with('\\') {
+            let escaped = after.chars().next().filter(|&c| is_escapable(c, true));
+            rest = &after[escaped.map_or(0, char::len_utf8)..];
+            continue;
+        }
+        let Some((code, len)) = legacy_code(after) else {
+            rest = after;
+            continue;
+        };
+        if text_start < marker_at {
+            segments.push(LegacySegment::Text(&input[text_start..marker_at]));
+        }
+        segments.push(LegacySegment::Code(code));
+        rest = &after[len..];
+        text_start = input.len() - rest.len();
+    }
+    if text_start < input.len() {
+        segments.push(LegacySegment::Text(&input[text_start..]));
+    }
+    segments
+}
+
+/// Checks whether a backslash before `c` escapes it in MiniMessage text: `<` and `\\` always
+/// need escaping, and the `&` and `§` markers do too when legacy codes are parsed
+fn is_escapable(c: char, legacy: bool) -> bool {
+    matches!(c, '<' | '\\') || (legacy && matches!(c, '&' | '§'))
+}
+
+/// Removes the backslashes escaping characters in `text`. A backslash before a character that
+/// doesn't need escaping is kept as is.
+fn unescape_text(text: &str, legacy: bool) -> Cow<'_, str> {
+    if !text.contains('\\') {
+        return Cow::Borrowed(text);
+    }
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\'
+            && let Some(&next) = chars.peek()
+            && is_escapable(next, legacy)
+        {
+            unescaped.push(next);
+            chars.next();
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Cow::Owned(unescaped)
+}
+
+/// Appends `text` to `output`, escaping the characters MiniMessage would otherwise treat
+/// specially
+fn push_escaped(output: &mut String, text: &str, legacy: bool) {
+    for c in text.chars() {
+        if is_escapable(c, legacy) {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+}
+
+/// Reads the code following a `§`/`&` marker, returning it with its length in bytes
+fn legacy_code(after_marker: &str) -> Option<(LegacyCode, usize)> {
+    // &#RRGGBB
+    if let Some(hex) = after_marker.strip_prefix('#')
+        && let Some(hex) = hex.get(..6)
+        && hex.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Some((LegacyCode::Color(Color::Hex(format!("#{hex}"))), 7));
+    }
+    // §x§R§R§G§G§B§B
+    if let Some(digits) = after_marker
+        .strip_prefix(['x', 'X'])
+        .and_then(|rest| legacy_hex_digits(rest))
+    {
+        let len = after_marker.len() - digits.1.len();
+        return Some((LegacyCode::Color(Color::Hex(format!("#{}", digits.0))), len));
+    }
+
+    let c = after_marker.chars().next()?.to_ascii_lowercase();
+    let code = match c {
+        'k' => LegacyCode::Decoration(TextDecoration::Obfuscated),
+        'l' => LegacyCode::Decoration(TextDecoration::Bold),
+        'm' => LegacyCode::Decoration(TextDecoration::Strikethrough),
+        'n' => LegacyCode::Decoration(TextDecoration::Underlined),
+        'o' => LegacyCode::Decoration(TextDecoration::Italic),
+        'r' => LegacyCode::Reset,
+        _ => {
+            let (_, named) = colors::LEGACY_CODE_TO_NAMED_COLOR
+                .iter()
+                .find(|(code, _)| *code == c)?;
+            LegacyCode::Color(Color::Named(*named))
+        }
+    };
+    Some((code, 1))
+}
+
+/// Reads the six `§R§R§G§G§B§B` pairs of the BungeeCord hex format, returning the hex digits
+/// and the remaining input
+fn legacy_hex_digits(mut rest: &str) -> Option<(String, &str)> {
+    let mut hex = String::with_capacity(6);
+    for _ in 0..6 {
+        rest = rest.strip_prefix(['§', '&'])?;
+        let digit = rest.chars().next().filter(char::is_ascii_hexdigit)?;
+        hex.push(digit.to_ascii_uppercase());
+        rest = &rest[1..];
+    }
+    Some((hex, rest))
+}
+
+/// MiniMessage tag name for a decoration
+fn decoration_tag(decoration: TextDecoration) -> &'static str {
+    match decoration {
+        TextDecoration::Bold => "bold",
+        TextDecoration::Italic => "italic",
+        TextDecoration::Underlined => "underlined",
+        TextDecoration::Strikethrough => "strikethrough",
+        TextDecoration::Obfuscated => "obfuscated",
+    }
+}
+
+/// Converts text using legacy `§`/`&` formatting codes into equivalent MiniMessage source.
+///
+/// Color codes become color tags (`&6` → `<gold>`, `&#FFAA00` → `<color:#FFAA00>`),
+/// formatting codes become decoration tags (`&l` → `<bold>`) and `&r` becomes `<reset>`.
+/// Since a legacy color code also clears any active formatting, a `<reset>` is inserted
+/// before a color tag that follows formatting codes.
+///
+/// ```
+/// use kyori_component_json::minimessage::legacy_to_minimessage;
+///
+/// assert_eq!(
+///     legacy_to_minimessage("&6Gold &lbold&c red"),
+///     "<gold>Gold <bold>bold<reset><red> red"
+/// );
+/// ```
+pub fn legacy_to_minimessage(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut decorated = false;
+    for segment in legacy_segments(input, false) {
+        match segment {
+            LegacySegment::Text(text) => push_escaped(&mut output, text, false),
+            LegacySegment::Code(LegacyCode::Color(color)) => {
+                if decorated {
+                    output.push_str("<reset>");
+                    decorated = false;
+                }
+                match color {
+                    Color::Named(named) => output.push_str(&format!("<{named}>")),
+                    Color::Hex(hex) => output.push_str(&format!("<color:{hex}>")),
+                }
+            }
+            LegacySegment::Code(LegacyCode::Decoration(decoration)) => {
+                output.push_str(&format!("<{}>", decoration_tag(decoration)));
+                decorated = true;
+            }
+            LegacySegment::Code(LegacyCode::Reset) => {
+                output.push_str("<reset>");
+                decorated = false;
+            }
+        }
+    }
+    output
 }
 
 impl Default for MiniMessage {
@@ -76,69 +486,246 @@ impl ComponentSerializer for MiniMessage {
     type Err = MiniMessageError;
 
     fn to_string(component: &Component) -> Result<String, Self::Err> {
-        Serializer::new().serialize(component)
+        MiniMessage::new().serialize(component)
     }
 }
 
-/// Internal parser state
-struct Parser<'a> {
-    input: &'a str,
-    position: usize,
-    config: &'a MiniMessageConfig,
-    style_stack: Vec<Style>,
-    component_parts: Vec<Component>,
+/// Builds a click event from `<click:action:value>` arguments, or `None` if they are invalid
+fn click_event_from_args(args: &[String]) -> Option<ClickEvent> {
+    let (action, value) = (args.first()?, args.get(1)?.clone());
+    Some(match action.as_str() {
+        "run_command" => ClickEvent::RunCommand { command: value },
+        "suggest_command" => ClickEvent::SuggestCommand { command: value },
+        "open_url" => ClickEvent::OpenUrl { url: value },
+        "open_file" => ClickEvent::OpenFile { path: value },
+        "change_page" => ClickEvent::ChangePage {
+            page: value.parse().ok()?,
+        },
+        "copy_to_clipboard" => ClickEvent::CopyToClipboard { value },
+        _ => return None,
+    })
 }
 
-impl<'a> Parser<'a> {
-    fn new(input: &'a str, config: &'a MiniMessageConfig) -> Self {
-        Self {
-            input,
-            position: 0,
-            config,
-            style_stack: vec![Style::default()],
-            component_parts: Vec::new(),
+/// Shadow opacity used by `<shadow:color>` when no alpha is given, matching Adventure
+const DEFAULT_SHADOW_ALPHA: f32 = 0.25;
+
+/// Builds a shadow color from `<shadow:#rrggbbaa>` or `<shadow:color[:alpha]>` arguments,
+/// or `None` if they are invalid. The alpha argument is a float in `[0, 1]`.
+fn shadow_color_from_args(args: &[String]) -> Option<ShadowColor> {
+    let color = args.first()?;
+    if let Some(hex) = color.strip_prefix('#')
+        && hex.len() == 8
+    {
+        if args.len() > 1 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
         }
+        let packed = u32::from_str_radix(hex, 16).ok()?;
+        return Some(ShadowColor::from_rgba(packed.to_be_bytes()));
     }
 
-    fn parse(&mut self) -> Result<Component, MiniMessageError> {
-        while self.position < self.input.len() {
-            if self.starts_with('<') {
-                self.parse_tag()?;
-            } else {
-                self.parse_text()?;
-            }
+    let [r, g, b] = color.parse::<Color>().ok()?.rgb()?;
+    let alpha = match args.get(1) {
+        Some(alpha) => alpha
+            .parse::<f32>()
+            .ok()
+            .filter(|a| (0.0..=1.0).contains(a))?,
+        None => DEFAULT_SHADOW_ALPHA,
+    };
+    if args.len() > 2 {
+        return None;
+    }
+    // In range after the check above, so the cast can't truncate
+    let a = (alpha * 255.0).round() as u8;
+    Some(ShadowColor::from_rgba([r, g, b, a]))
+}
+
+/// Builds the color stops and phase of a gradient from `<gradient[:color...][:phase]>`
+/// arguments, or `None` if they are invalid.
+///
+/// Stops can be named or hex colors, and default to white to black. The optional phase is a
+/// float in `[-1, 1]` that shifts where the gradient starts.
+fn gradient_from_args(args: &[String]) -> Option<(Vec<Color>, f32)> {
+    let (stops, phase) = match args.split_last() {
+        Some((last, stops)) if last.parse::<f32>().is_ok() => {
+            let phase = last
+                .parse::<f32>()
+                .ok()
+                .filter(|p| (-1.0..=1.0).contains(p))?;
+            (stops, phase)
+        }
+        _ => (args, 0.0),
+    };
+    let stops = stops
+        .iter()
+        .map(|stop| stop.parse::<Color>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    if stops.is_empty() {
+        let default = [NamedColor::White, NamedColor::Black].map(Color::Named);
+        return Some((default.to_vec(), phase));
+    }
+    Some((stops, phase))
+}
+
+/// Gets the color at `t` in `[0, 1]` along a gradient with evenly spaced `stops`
+fn gradient_color(stops: &[Color], t: f32) -> Color {
+    match stops {
+        [] => Color::Named(NamedColor::White),
+        [only] => only.clone(),
+        _ => {
+            let scaled = t * (stops.len() - 1) as f32;
+            // `t` is in `[0, 1]`, so the cast can't truncate
+            let index = (scaled.floor() as usize).min(stops.len() - 2);
+            stops[index].lerp(&stops[index + 1], scaled - index as f32)
         }
+    }
+}
 
-        let parts = std::mem::take(&mut self.component_parts);
-        if parts.len() == 1 {
-            // SAFETY: This is safe because we always have at least one style
-            Ok(parts.into_iter().next().unwrap())
+/// Tags that insert a non-text component, like `<lang:key>`, rather than opening a style
+const CONTENT_TAGS: [&str; 12] = [
+    "lang",
+    "tr",
+    "translate",
+    "lang_or",
+    "tr_or",
+    "translate_or",
+    "key",
+    "selector",
+    "sel",
+    "score",
+    "nbt",
+    "data",
+];
+
+/// Quotes a tag argument if it would otherwise be split or cut short by the tokenizer, so
+/// simple arguments stay unquoted and readable.
+///
+/// `rest_of_tag` is for values that run to the end of the tag, like the command in
+/// `<click:run_command:/spawn>` (see [`MiniMessageTokenizer::takes_rest_of_tag`]). They can
+/// hold `/` and `<` unquoted, as long as they don't end in `/`.
+fn quote_arg(arg: &str, rest_of_tag: bool) -> String {
+    let is_delimiter = |c: char| matches!(c, ':' | '>' | '\'' | '"' | '\\') || c.is_whitespace();
+    let needs_quotes = arg.is_empty()
+        || if rest_of_tag {
+            arg.contains(is_delimiter) || arg.ends_with('/')
         } else {
-            Ok(Component::Array(parts))
+            arg.contains(|c| is_delimiter(c) || matches!(c, '<' | '/'))
+        };
+    if !needs_quotes {
+        return arg.to_string();
+    }
+    let mut quoted = String::with_capacity(arg.len() + 2);
+    quoted.push('\'');
+    for c in arg.chars() {
+        if c == '\'' || c == '\\' {
+            quoted.push('\\');
         }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Reconstructs the source text of a tag, used when it is kept as literal text
+fn tag_source(tag: &str, args: &[String], self_closing: bool) -> String {
+    let mut tag_text = format!("<{tag}");
+    for arg in args {
+        tag_text.push(':');
+        tag_text.push_str(arg);
+    }
+    if self_closing {
+        tag_text.push('/');
+    }
+    tag_text.push('>');
+    tag_text
+}
+
+/// A lexical token produced by [`MiniMessageTokenizer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// A run of literal text between tags, as written in the input: escapes such as `\<` are
+    /// kept and only resolved by the parser
+    Text(&'a str),
+    /// An opening tag such as `<red>` or `<click:run_command:/help>`
+    OpenTag {
+        /// Lowercased tag name
+        name: String,
+        /// Colon-separated arguments, with quotes removed
+        args: Vec<String>,
+    },
+    /// A closing tag such as `</red>`, holding the lowercased tag name
+    CloseTag(String),
+    /// A self-closing tag such as `<newline/>`
+    SelfClosing {
+        /// Lowercased tag name
+        name: String,
+        /// Colon-separated arguments, with quotes removed
+        args: Vec<String>,
+    },
+}
+
+/// Pull-style tokenizer splitting MiniMessage input into [`Token`]s.
+///
+/// This only performs lexing, so it can be used for syntax highlighting without building
+/// components. Iteration stops after the first error.
+///
+/// ```
+/// use kyori_component_json::minimessage::{MiniMessageTokenizer, Token};
+///
+/// let tokens: Vec<_> = MiniMessageTokenizer::new("<red>Hi</red>")
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(tokens[1], Token::Text("Hi"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MiniMessageTokenizer<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> MiniMessageTokenizer<'a> {
+    /// Creates a tokenizer over `input`.
+    pub fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
     }
 
-    fn parse_text(&mut self) -> Result<(), MiniMessageError> {
+    /// Byte offset of the next token in the input.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn read_text(&mut self) -> Token<'a> {
         let start = self.position;
-        while self.position < self.input.len() {
-            if self.starts_with('<') || (self.config.parse_legacy_colors && self.starts_with('&')) {
-                break;
+        let rest = &self.input[start..];
+        let mut end = rest.len();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '<' if self.tag_at(start + i) => {
+                    end = i;
+                    break;
+                }
+                // An escaped `<` or `\` doesn't end the text
+                '\\' if rest[i + 1..].starts_with(['<', '\\']) => {
+                    chars.next();
+                }
+                _ => {}
             }
-            self.position += 1;
         }
+        self.position = start + end;
+        Token::Text(&self.input[start..self.position])
+    }
 
-        if start < self.position {
-            let text = &self.input[start..self.position];
-            let current_style = self.current_style();
-            let mut comp = Component::text(text);
-            comp = comp.color(current_style.color.clone());
-            comp = comp.decorations(&self.collect_decorations());
-            self.component_parts.push(comp);
-        }
-        Ok(())
+    /// Checks whether the `<` at byte offset `at` starts a tag: it has to be followed by a
+    /// letter, optionally after the `/` of a closing tag and the `!` of a negated decoration.
+    /// Any other `<`, as in `5 < 10` or `<3`, is literal text.
+    fn tag_at(&self, at: usize) -> bool {
+        let after = &self.input[at + 1..];
+        let after = after.strip_prefix('/').unwrap_or(after);
+        let name = after.strip_prefix('!').unwrap_or(after);
+        name.starts_with(|c: char| c.is_ascii_alphabetic())
     }
 
-    fn parse_tag(&mut self) -> Result<(), MiniMessageError> {
+    fn read_tag(&mut self) -> Result<Token<'a>, MiniMessageError> {
         // Skip '<'
         self.position += 1;
 
@@ -146,48 +733,95 @@ impl<'a> Parser<'a> {
             // Closing tag
             self.position += 1;
             let tag_name = self.read_tag_name()?;
-            self.handle_close_tag(&tag_name)?;
             self.expect('>')?;
-        } else {
-            // Opening tag
-            let tag_name = self.read_tag_name()?;
-            let mut args = Vec::new();
-            let mut self_closing = false;
+            return Ok(Token::CloseTag(tag_name));
+        }
 
-            while self.position < self.input.len() {
-                // skip whitespace
+        // Opening tag
+        let tag_name = self.read_tag_name()?;
+        let mut args = Vec::new();
+
+        while self.position < self.input.len() {
+            // skip whitespace
+            self.skip_whitespace();
+            // skip colon separators
+            while self.starts_with(':') {
+                self.position += 1;
                 self.skip_whitespace();
-                // skip colon separators
-                while self.starts_with(':') {
-                    self.position += 1;
-                    self.skip_whitespace();
-                }
+            }
 
-                // if we’ve hit the end of the tag, stop
-                if self.starts_with('>') || self.starts_with('/') {
+            if Self::takes_rest_of_tag(&tag_name, &args) && !self.starts_with_quote() {
+                let value = self.read_rest_of_tag();
+                if value.is_empty() {
                     break;
                 }
-
-                // read an argument
-                let arg = self.read_argument()?;
-                args.push(arg);
+                args.push(value);
+                continue;
             }
 
-            // Check for self-closing tag
-            if self.starts_with('/') {
-                self.position += 1;
-                self_closing = true;
+            // if we’ve hit the end of the tag, stop
+            if self.starts_with('>') || self.starts_with('/') {
+                break;
             }
-            self.expect('>')?;
 
-            self.handle_open_tag(&tag_name, args, self_closing)?;
+            // read an argument
+            let arg = self.read_argument()?;
+            args.push(arg);
         }
 
-        Ok(())
+        // Check for self-closing tag
+        let self_closing = self.starts_with('/');
+        if self_closing {
+            self.position += 1;
+        }
+        self.expect('>')?;
+
+        if self_closing {
+            Ok(Token::SelfClosing {
+                name: tag_name,
+                args,
+            })
+        } else {
+            Ok(Token::OpenTag {
+                name: tag_name,
+                args,
+            })
+        }
+    }
+
+    /// Whether the next argument of `tag` is a value that runs to the end of the tag, like the
+    /// URL in `<click:open_url:https://example.com>`, so it can hold `:` and `/` unquoted
+    fn takes_rest_of_tag(tag: &str, args: &[String]) -> bool {
+        match (tag, args) {
+            ("click", [_action]) => true,
+            ("hover", [action]) => action == "show_text",
+            _ => false,
+        }
+    }
+
+    /// Reads an unquoted value up to the closing `>`. A `/` right before the `>` still makes
+    /// the tag self-closing, so a value ending in `/` has to be quoted.
+    fn read_rest_of_tag(&mut self) -> String {
+        let start = self.position;
+        let rest = &self.input[start..];
+        let mut end = rest.find('>').unwrap_or(rest.len());
+        if rest[..end].ends_with('/') {
+            end -= 1;
+        }
+        self.position = start + end;
+        rest[..end].trim_end().to_string()
+    }
+
+    fn starts_with_quote(&self) -> bool {
+        self.starts_with('\'') || self.starts_with('"')
     }
 
     fn read_tag_name(&mut self) -> Result<String, MiniMessageError> {
         let start = self.position;
+        // Negated decorations, e.g. `<!bold>`
+        if self.starts_with('!') {
+            self.position += 1;
+        }
         while self.position < self.input.len() {
             let c = self.current_char();
             if !c.is_ascii_alphanumeric() && c != '_' && c != '-' {
@@ -195,14 +829,17 @@ impl<'a> Parser<'a> {
             }
             self.position += 1;
         }
-        if start == self.position {
-            return Err(MiniMessageError("Expected tag name".to_string()));
+        if self.input[start..self.position]
+            .trim_start_matches('!')
+            .is_empty()
+        {
+            return Err(MiniMessageError::new("Expected tag name"));
         }
         Ok(self.input[start..self.position].to_lowercase())
     }
 
     fn read_argument(&mut self) -> Result<String, MiniMessageError> {
-        if self.starts_with('\'') || self.starts_with('"') {
+        if self.starts_with_quote() {
             self.read_quoted_string()
         } else {
             self.read_unquoted_string()
@@ -229,10 +866,10 @@ impl<'a> Parser<'a> {
             } else {
                 result.push(c);
             }
-            self.position += 1;
+            self.position += c.len_utf8();
         }
 
-        Err(MiniMessageError("Unterminated quoted string".to_string()))
+        Err(MiniMessageError::new("Unterminated quoted string"))
     }
 
     fn read_unquoted_string(&mut self) -> Result<String, MiniMessageError> {
@@ -242,21 +879,293 @@ impl<'a> Parser<'a> {
             if c == ':' || c == '>' || c == '/' || c.is_whitespace() {
                 break;
             }
-            self.position += 1;
+            self.position += c.len_utf8();
         }
-        // what?
-        // if start == self.position {
-        //     return Err(MiniMessageError("Expected argument".to_string()));
-        // }
         Ok(self.input[start..self.position].to_string())
     }
 
+    fn starts_with(&self, c: char) -> bool {
+        self.input[self.position..].starts_with(c)
+    }
+
+    fn current_char(&self) -> char {
+        self.input[self.position..].chars().next().unwrap_or('\0')
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.input[self.position..].chars().next()
+            && c.is_whitespace()
+        {
+            self.position += c.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), MiniMessageError> {
+        if self.position < self.input.len() && self.current_char() == c {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(MiniMessageError::new(format!("Expected '{c}'")))
+        }
+    }
+}
+
+impl<'a> Iterator for MiniMessageTokenizer<'a> {
+    type Item = Result<Token<'a>, MiniMessageError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.input.len() {
+            return None;
+        }
+        if !self.starts_with('<') || !self.tag_at(self.position) {
+            return Some(Ok(self.read_text()));
+        }
+        let token = self.read_tag();
+        if token.is_err() {
+            // Stop iterating after an error
+            self.position = self.input.len();
+        }
+        Some(token)
+    }
+}
+
+/// A style pushed by a tag or a legacy formatting code
+struct StyleFrame {
+    style: Style,
+    /// Pushed by a self-closing tag (e.g. `<red/>`) or a legacy code, so no closing tag will
+    /// pop it; it lasts until the enclosing tag closes
+    sticky: bool,
+    /// Canonical name of the tag that pushed this frame (see [`canonical_tag_name`]), or
+    /// `None` for the base frame and legacy codes
+    tag: Option<String>,
+}
+
+/// Moves `style` from `old_parent` onto `new_parent`: the properties `style` changed from
+/// `old_parent` are kept, and the rest are inherited from `new_parent`
+fn rebase_style(style: &Style, old_parent: &Style, new_parent: &Style) -> Style {
+    macro_rules! rebase {
+        ($($field:ident),*) => {
+            Style {
+                $($field: if style.$field == old_parent.$field {
+                    new_parent.$field.clone()
+                } else {
+                    style.$field.clone()
+                },)*
+            }
+        };
+    }
+    rebase!(
+        color,
+        font,
+        bold,
+        italic,
+        underlined,
+        strikethrough,
+        obfuscated,
+        shadow_color,
+        insertion,
+        click_event,
+        hover_event
+    )
+}
+
+/// A `<gradient>` tag whose text is colored once it closes, when its length is known
+struct GradientSpan {
+    stops: Vec<Color>,
+    phase: f32,
+    /// Length of the style stack before the tag pushed its frame; the tag is closed once the
+    /// stack is back to this length
+    depth: usize,
+    /// Index of the first component part inside the tag
+    start: usize,
+    /// Parts already colored by gradients nested in this one or by a color tag inside it,
+    /// which keep their colors
+    nested: Vec<Range<usize>>,
+}
+
+/// Resolves tag aliases, so e.g. `<b>` can be closed by `</bold>`
+fn canonical_tag_name(tag: &str) -> &str {
+    match tag {
+        "b" => "bold",
+        "i" | "em" => "italic",
+        "u" => "underlined",
+        "st" => "strikethrough",
+        "obf" => "obfuscated",
+        "!b" => "!bold",
+        "!i" | "!em" => "!italic",
+        "!u" => "!underlined",
+        "!st" => "!strikethrough",
+        "!obf" => "!obfuscated",
+        "colour" | "c" => "color",
+        "insert" => "insertion",
+        _ => tag,
+    }
+}
+
+/// Internal parser state, building components from tokens
+struct Parser<'a> {
+    tokens: MiniMessageTokenizer<'a>,
+    config: &'a MiniMessageConfig,
+    style_stack: Vec<StyleFrame>,
+    component_parts: Vec<Component>,
+    /// Gradients still open, innermost last
+    gradients: Vec<GradientSpan>,
+    /// Errors recovered from so far, or `None` to stop at the first error
+    diagnostics: Option<Vec<MiniMessageError>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str, config: &'a MiniMessageConfig) -> Self {
+        Self {
+            tokens: MiniMessageTokenizer::new(input),
+            config,
+            style_stack: vec![StyleFrame {
+                style: Style::default(),
+                sticky: false,
+                tag: None,
+            }],
+            component_parts: Vec::new(),
+            gradients: Vec::new(),
+            diagnostics: None,
+        }
+    }
+
+    fn parse(&mut self) -> Result<Component, MiniMessageError> {
+        loop {
+            let start = self.tokens.position();
+            let Some(token) = self.tokens.next() else {
+                break;
+            };
+            if let Err(err) = token.and_then(|token| self.handle_token(token)) {
+                // Nested parsers (e.g. for hover text) set their own position first, so the
+                // outermost one wins and the position refers to the caller's input
+                let err = err.at(start);
+                let Some(diagnostics) = &mut self.diagnostics else {
+                    return Err(err);
+                };
+                diagnostics.push(err);
+                // Keep the offending `<` as text and resume right after it
+                self.tokens.position = start + 1;
+                self.push_text("<");
+            }
+        }
+
+        // Gradients left open run to the end of the input
+        self.finish_gradients(0);
+        let mut parts = std::mem::take(&mut self.component_parts);
+        if parts.len() == 1
+            && let Some(part) = parts.pop()
+        {
+            return Ok(part);
+        }
+        // The rest of an array is styled like its first element, so a styled first part
+        // needs an unstyled one in front to keep its style to itself
+        if parts.first().is_some_and(Component::has_styling) {
+            parts.insert(0, Component::String(String::new()));
+        }
+        Ok(Component::Array(parts))
+    }
+
+    fn handle_token(&mut self, token: Token<'_>) -> Result<(), MiniMessageError> {
+        match token {
+            Token::Text(text) => self.push_source_text(text),
+            Token::OpenTag { name, args } => {
+                let (name, args) = self.resolve_alias(name, args)?;
+                self.handle_open_tag(&name, args, false)
+            }
+            Token::SelfClosing { name, args } => {
+                let (name, args) = self.resolve_alias(name, args)?;
+                self.handle_open_tag(&name, args, true)
+            }
+            Token::CloseTag(name) => {
+                let (name, _) = self.resolve_alias(name, Vec::new())?;
+                self.handle_close_tag(&name)
+            }
+        }
+    }
+
+    /// Replaces a tag configured in [`MiniMessageConfig::tag_aliases`] by its target, with
+    /// the alias's arguments after the target's own
+    fn resolve_alias(
+        &self,
+        name: String,
+        args: Vec<String>,
+    ) -> Result<(String, Vec<String>), MiniMessageError> {
+        let Some(target) = self.config.tag_aliases.get(&name) else {
+            return Ok((name, args));
+        };
+        match MiniMessageTokenizer::new(&format!("<{target}>")).next() {
+            Some(Ok(Token::OpenTag {
+                name: target_name,
+                args: mut target_args,
+            })) => {
+                target_args.extend(args);
+                Ok((target_name, target_args))
+            }
+            _ => Err(MiniMessageError::new(format!(
+                "Alias '{name}' has an invalid target '{target}'"
+            ))),
+        }
+    }
+
+    /// Pushes a text component styled with the current style
+    fn push_text(&mut self, text: &str) {
+        // Text given its own color inside a gradient keeps it
+        let own_color = self.gradients.last().is_some_and(|span| {
+            self.style_stack
+                .get(span.depth)
+                .is_some_and(|frame| frame.style.color != self.current_style().color)
+        });
+        let index = self.component_parts.len();
+        if own_color && let Some(span) = self.gradients.last_mut() {
+            span.nested.push(index..index + 1);
+        }
+        self.component_parts
+            .push(Component::text(text).apply_fallback_style(self.current_style()));
+    }
+
+    /// Pushes text from the input, applying legacy formatting codes in it when enabled
+    fn push_source_text(&mut self, text: &str) -> Result<(), MiniMessageError> {
+        if !self.config.parse_legacy_colors {
+            self.push_text(&unescape_text(text, false));
+            return Ok(());
+        }
+        for segment in legacy_segments(text, true) {
+            let depth = self.style_stack.len();
+            match segment {
+                LegacySegment::Text(text) => self.push_text(&unescape_text(text, true)),
+                // A legacy color code also clears formatting
+                LegacySegment::Code(LegacyCode::Color(color)) => self.push_style(|s| {
+                    s.color = Some(color);
+                    s.bold = None;
+                    s.italic = None;
+                    s.underlined = None;
+                    s.strikethrough = None;
+                    s.obfuscated = None;
+                })?,
+                LegacySegment::Code(LegacyCode::Decoration(decoration)) => {
+                    self.push_style(|s| match decoration {
+                        TextDecoration::Bold => s.bold = Some(true),
+                        TextDecoration::Italic => s.italic = Some(true),
+                        TextDecoration::Underlined => s.underlined = Some(true),
+                        TextDecoration::Strikethrough => s.strikethrough = Some(true),
+                        TextDecoration::Obfuscated => s.obfuscated = Some(true),
+                    })?
+                }
+                LegacySegment::Code(LegacyCode::Reset) => self.reset_style()?,
+            }
+            self.mark_sticky(depth);
+        }
+        Ok(())
+    }
+
     fn handle_open_tag(
         &mut self,
         tag: &str,
         args: Vec<String>,
         self_closing: bool,
     ) -> Result<(), MiniMessageError> {
+        let depth = self.style_stack.len();
         match tag {
             // Colors
             "black" => self.push_style(|s| s.color = Some(Color::Named(NamedColor::Black)))?,
@@ -287,11 +1196,10 @@ impl<'a> Parser<'a> {
             }
             "yellow" => self.push_style(|s| s.color = Some(Color::Named(NamedColor::Yellow)))?,
             "white" => self.push_style(|s| s.color = Some(Color::Named(NamedColor::White)))?,
-            "color" | "colour" | "c" if !args.is_empty() => {
-                if let Ok(color) = args[0].parse::<Color>() {
-                    self.push_style(|s| s.color = Some(color))?
-                }
-            }
+            "color" | "colour" | "c" if !args.is_empty() => match args[0].parse::<Color>() {
+                Ok(color) => self.push_style(|s| s.color = Some(color))?,
+                Err(_) => self.reject_tag(tag, &args, self_closing, "Invalid color")?,
+            },
 
             // Decorations
             "bold" | "b" => self.push_style(|s| s.bold = Some(true))?,
@@ -299,183 +1207,464 @@ impl<'a> Parser<'a> {
             "underlined" | "u" => self.push_style(|s| s.underlined = Some(true))?,
             "strikethrough" | "st" => self.push_style(|s| s.strikethrough = Some(true))?,
             "obfuscated" | "obf" => self.push_style(|s| s.obfuscated = Some(true))?,
+            "!bold" | "!b" => self.push_style(|s| s.bold = Some(false))?,
+            "!italic" | "!i" | "!em" => self.push_style(|s| s.italic = Some(false))?,
+            "!underlined" | "!u" => self.push_style(|s| s.underlined = Some(false))?,
+            "!strikethrough" | "!st" => self.push_style(|s| s.strikethrough = Some(false))?,
+            "!obfuscated" | "!obf" => self.push_style(|s| s.obfuscated = Some(false))?,
+
+            // Text shadow
+            "shadow" => match shadow_color_from_args(&args) {
+                Some(shadow) => self.push_style(|s| s.shadow_color = Some(shadow))?,
+                None => self.reject_tag(tag, &args, self_closing, "Invalid shadow color")?,
+            },
+
+            // Gradients
+            "gradient" => match gradient_from_args(&args) {
+                Some((stops, phase)) => {
+                    self.push_style(|_| {})?;
+                    self.gradients.push(GradientSpan {
+                        stops,
+                        phase,
+                        depth,
+                        start: self.component_parts.len(),
+                        nested: Vec::new(),
+                    });
+                }
+                None => self.reject_tag(tag, &args, self_closing, "Invalid gradient")?,
+            },
 
             // Reset tag
             "reset" => self.reset_style()?,
 
             // Click events
-            "click" if args.len() >= 2 => {
-                let action = args[0].as_str();
-                let value = args[1].as_str();
-                match action {
-                    "run_command" => self.push_style(|s| {
-                        s.click_event = Some(ClickEvent::RunCommand {
-                            command: value.to_string(),
-                        })
-                    })?,
-                    "suggest_command" => self.push_style(|s| {
-                        s.click_event = Some(ClickEvent::SuggestCommand {
-                            command: value.to_string(),
-                        })
-                    })?,
-                    "open_url" => self.push_style(|s| {
-                        s.click_event = Some(ClickEvent::OpenUrl {
-                            url: value.to_string(),
-                        })
-                    })?,
-                    "copy_to_clipboard" => self.push_style(|s| {
-                        s.click_event = Some(ClickEvent::CopyToClipboard {
-                            value: value.to_string(),
-                        })
-                    })?,
-                    _ => {}
-                }
-            }
+            "click" => match click_event_from_args(&args) {
+                Some(event) => self.push_style(|s| s.click_event = Some(event))?,
+                None => self.reject_tag(tag, &args, self_closing, "Invalid click action")?,
+            },
 
             // Hover events
-            "hover" if !args.is_empty() => {
-                let action = args[0].as_str();
-                if action == "show_text" && args.len() >= 2 {
-                    let mut nested_parser = Parser::new(&args[1], self.config);
-                    let nested = nested_parser.parse()?;
-                    self.push_style(|s| {
-                        s.hover_event = Some(HoverEvent::ShowText { value: nested })
-                    })?;
-                }
-            }
+            "hover" => match self.hover_event_from_args(&args)? {
+                Some(event) => self.push_style(|s| s.hover_event = Some(event))?,
+                None => self.reject_tag(tag, &args, self_closing, "Invalid hover action")?,
+            },
 
             // Newline
             "newline" | "br" => {
-                self.component_parts.push(Component::text("\n"));
+                self.component_parts.push(Component::newline());
             }
 
+            // Translations, keybinds, selectors, scores and NBT
+            _ if CONTENT_TAGS.contains(&tag) => match self.content_from_args(tag, &args)? {
+                Some(content) => {
+                    let content = Component::Object(Box::new(content));
+                    self.component_parts
+                        .push(content.apply_fallback_style(self.current_style()));
+                }
+                None => self.reject_tag(tag, &args, self_closing, "Invalid arguments")?,
+            },
+
             // Insertion
             "insert" | "insertion" if !args.is_empty() => {
                 self.push_style(|s| s.insertion = Some(args[0].clone()))?
             }
 
-            // Handle self-closing tags
-            _ if self_closing => {
-                // For self-closing tags, create an empty component with the style
-                let current_style = self.current_style();
-                let mut comp = Component::text("");
-                comp = comp.color(current_style.color.clone());
-                comp = comp.decorations(&self.collect_decorations());
-                self.component_parts.push(comp);
+            // Unknown tags are handled according to the configured policy
+            _ => match self.config.unknown_tags {
+                UnknownTagPolicy::Error => {
+                    return Err(MiniMessageError::new(format!("Unknown tag '{tag}'")));
+                }
+                UnknownTagPolicy::AsText => {
+                    self.push_text(&tag_source(tag, &args, self_closing));
+                }
+                UnknownTagPolicy::Strip => {
+                    if !self_closing {
+                        self.skip_to_close_tag(tag);
+                    }
+                }
+            },
+        }
+
+        let name = canonical_tag_name(tag);
+        for frame in self.style_stack.iter_mut().skip(depth) {
+            frame.tag = Some(name.to_string());
+        }
+        // A self-closing style tag styles the rest of its enclosing tag, e.g. `a<red/>b`
+        // colors only `b`
+        if self_closing {
+            self.mark_sticky(depth);
+        }
+        Ok(())
+    }
+
+    fn handle_close_tag(&mut self, tag: &str) -> Result<(), MiniMessageError> {
+        match tag {
+            "bold" | "b" | "italic" | "i" | "em" | "underlined" | "u" | "strikethrough" | "st"
+            | "obfuscated" | "obf" | "!bold" | "!b" | "!italic" | "!i" | "!em" | "!underlined"
+            | "!u" | "!strikethrough" | "!st" | "!obfuscated" | "!obf" | "color" | "colour"
+            | "c" | "shadow" | "click" | "hover" | "insert" | "insertion" | "gradient" => {
+                self.pop_style(tag)?;
             }
+            _ if tag.parse::<NamedColor>().is_ok() => {
+                self.pop_style(tag)?;
+            }
+            // Tags that never open a style have nothing to close
+            "newline" | "br" | "reset" => {}
+            _ if CONTENT_TAGS.contains(&tag) => {}
+            _ => match self.config.unknown_tags {
+                UnknownTagPolicy::Error => {
+                    return Err(MiniMessageError::new(format!(
+                        "Unknown closing tag '{tag}'"
+                    )));
+                }
+                UnknownTagPolicy::AsText => self.push_text(&format!("</{tag}>")),
+                // The content was already skipped when the tag was opened
+                UnknownTagPolicy::Strip => {}
+            },
+        }
+        Ok(())
+    }
 
-            // Unknown tags are treated as text
-            _ => {
-                let mut tag_text = format!("<{tag}");
-                for arg in args {
-                    tag_text.push(':');
-                    tag_text.push_str(&arg);
+    /// Handles a known tag whose arguments are invalid: an error in strict mode,
+    /// otherwise the tag is kept as literal text.
+    fn reject_tag(
+        &mut self,
+        tag: &str,
+        args: &[String],
+        self_closing: bool,
+        reason: &str,
+    ) -> Result<(), MiniMessageError> {
+        if self.config.strict {
+            return Err(MiniMessageError::new(format!(
+                "{reason} in '{}'",
+                tag_source(tag, args, self_closing)
+            )));
+        }
+        self.push_text(&tag_source(tag, args, self_closing));
+        if !self_closing && !CONTENT_TAGS.contains(&tag) {
+            // Push an unchanged frame so the matching closing tag stays balanced
+            self.push_style(|_| {})?;
+        }
+        Ok(())
+    }
+
+    /// Builds the component inserted by a content tag, or `None` if its arguments are invalid:
+    ///
+    /// - `<lang:key:args...>` and `<lang_or:key:fallback:args...>` for translations, with each
+    ///   argument parsed as MiniMessage
+    /// - `<key:name>` for keybinds
+    /// - `<selector:pattern[:separator]>` for entity selectors
+    /// - `<score:name:objective>` for scoreboard values
+    /// - `<nbt:block|entity|storage:id:path[:separator][:interpret]>` for NBT values
+    fn content_from_args(
+        &self,
+        tag: &str,
+        args: &[String],
+    ) -> Result<Option<ComponentObject>, MiniMessageError> {
+        let Some(first) = args.first() else {
+            return Ok(None);
+        };
+        let parse = |input: &str| Parser::new(input, self.config).parse();
+        let mut obj = ComponentObject::default();
+        match tag {
+            "lang" | "tr" | "translate" | "lang_or" | "tr_or" | "translate_or" => {
+                let mut rest = &args[1..];
+                if tag.ends_with("_or") {
+                    let Some((fallback, after)) = rest.split_first() else {
+                        return Ok(None);
+                    };
+                    obj.fallback = Some(fallback.clone());
+                    rest = after;
                 }
-                if self_closing {
-                    tag_text.push('/');
+                obj.translate = Some(first.clone());
+                if !rest.is_empty() {
+                    let with = rest
+                        .iter()
+                        .map(|arg| parse(arg))
+                        .collect::<Result<_, _>>()?;
+                    obj.with = Some(with);
                 }
-                tag_text.push('>');
-                self.component_parts
-                    .push(Component::text(tag_text).apply_fallback_style(self.current_style()));
             }
+            "key" if args.len() == 1 => obj.keybind = Some(first.clone()),
+            "selector" | "sel" if args.len() <= 2 => {
+                obj.selector = Some(first.clone());
+                if let Some(separator) = args.get(1) {
+                    obj.separator = Some(Box::new(parse(separator)?));
+                }
+            }
+            "score" if args.len() == 2 => {
+                obj.score = Some(ScoreContent::new(first.clone(), args[1].clone()));
+            }
+            "nbt" | "data" => {
+                let (Some(id), Some(path)) = (args.get(1), args.get(2)) else {
+                    return Ok(None);
+                };
+                match first.as_str() {
+                    "block" => obj.block = Some(id.clone()),
+                    "entity" => obj.entity = Some(id.clone()),
+                    "storage" => obj.storage = Some(id.clone()),
+                    _ => return Ok(None),
+                }
+                obj.nbt = Some(path.clone());
+                let mut rest = &args[3..];
+                if rest.last().is_some_and(|arg| arg == "interpret") {
+                    obj.interpret = Some(true);
+                    rest = &rest[..rest.len() - 1];
+                }
+                match rest {
+                    [] => {}
+                    [separator] => obj.separator = Some(Box::new(parse(separator)?)),
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
         }
+        Ok(Some(obj))
+    }
 
-        Ok(())
+    /// Builds a hover event from `<hover:action:...>` arguments, or `None` if they are invalid
+    fn hover_event_from_args(
+        &self,
+        args: &[String],
+    ) -> Result<Option<HoverEvent>, MiniMessageError> {
+        let (Some(action), Some(value)) = (args.first(), args.get(1)) else {
+            return Ok(None);
+        };
+        let event = match action.as_str() {
+            "show_text" => {
+                let value = Parser::new(value, self.config).parse()?;
+                HoverEvent::ShowText { value }
+            }
+            "show_item" => {
+                let count = match args.get(2) {
+                    Some(count) => match count.parse() {
+                        Ok(count) => Some(count),
+                        Err(_) => return Ok(None),
+                    },
+                    None => None,
+                };
+                HoverEvent::ShowItem {
+                    id: value.clone(),
+                    count,
+                    components: None,
+                }
+            }
+            "show_entity" => {
+                let Some(uuid) = args.get(2) else {
+                    return Ok(None);
+                };
+                let name = match args.get(3) {
+                    Some(name) => Some(Parser::new(name, self.config).parse()?),
+                    None => None,
+                };
+                HoverEvent::ShowEntity {
+                    name,
+                    id: value.clone(),
+                    uuid: UuidRepr::String(uuid.clone()),
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
     }
 
-    fn handle_close_tag(&mut self, tag: &str) -> Result<(), MiniMessageError> {
-        match tag {
-            "bold" | "b" | "italic" | "i" | "em" | "underlined" | "u" | "strikethrough" | "st"
-            | "obfuscated" | "obf" | "color" | "colour" | "c" | "click" | "hover" | "insert"
-            | "insertion" => {
-                self.pop_style()?;
-            }
-            _ => {
-                // For unknown tags, just pop the style anyway
-                if self.style_stack.len() > 1 {
-                    self.style_stack.pop();
+    /// Consumes tokens up to and including the closing tag matching `tag`, accounting for
+    /// nested tags of the same name. Consumes nothing if there is no matching closing tag.
+    fn skip_to_close_tag(&mut self, tag: &str) {
+        let mut lookahead = self.tokens.clone();
+        let mut depth = 0usize;
+        while let Some(Ok(token)) = lookahead.next() {
+            match token {
+                Token::OpenTag { name, .. } if name == tag => depth += 1,
+                Token::CloseTag(name) if name == tag => {
+                    if depth == 0 {
+                        self.tokens = lookahead;
+                        return;
+                    }
+                    depth -= 1;
                 }
+                _ => {}
             }
         }
-        Ok(())
     }
 
     fn push_style<F>(&mut self, modifier: F) -> Result<(), MiniMessageError>
     where
         F: FnOnce(&mut Style),
     {
-        let mut new_style = self.current_style().clone();
-        modifier(&mut new_style);
-        self.style_stack.push(new_style);
+        let mut style = self.current_style().clone();
+        modifier(&mut style);
+        self.style_stack.push(StyleFrame {
+            style,
+            sticky: false,
+            tag: None,
+        });
         Ok(())
     }
 
-    fn pop_style(&mut self) -> Result<(), MiniMessageError> {
+    /// Marks the frames pushed above `depth` as sticky
+    fn mark_sticky(&mut self, depth: usize) {
+        for frame in self.style_stack.iter_mut().skip(depth) {
+            frame.sticky = true;
+        }
+    }
+
+    /// Closes the innermost open `tag` (see [`Self::close_frame`]).
+    ///
+    /// In strict mode, only sticky frames may be left open above it, so `<red>hi</blue>`
+    /// is an error. Otherwise a closing tag that matches nothing open pops the innermost
+    /// open tag, along with any sticky frames above it.
+    fn pop_style(&mut self, tag: &str) -> Result<(), MiniMessageError> {
+        let name = canonical_tag_name(tag);
+        let matching = self
+            .style_stack
+            .iter()
+            .rposition(|frame| frame.tag.as_deref() == Some(name));
+
+        if self.config.strict {
+            let innermost_open = self.style_stack[1..]
+                .iter()
+                .rposition(|frame| !frame.sticky);
+            // The match must be the innermost open tag, or sticky with nothing open above it
+            let balanced = match (matching, innermost_open) {
+                (Some(index), Some(open)) => open < index,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if !balanced {
+                let open = innermost_open
+                    .and_then(|open| self.style_stack[open + 1].tag.as_deref())
+                    .unwrap_or_default();
+                return Err(MiniMessageError::new(if open.is_empty() {
+                    format!("Unbalanced closing tag '</{tag}>'")
+                } else {
+                    format!("Closing tag '</{tag}>' does not match open tag '<{open}>'")
+                }));
+            }
+        }
+
+        if let Some(index) = matching {
+            self.close_frame(index);
+            return Ok(());
+        }
+
+        let mut popped_sticky = false;
+        while self.style_stack.len() > 1 && self.style_stack.last().is_some_and(|f| f.sticky) {
+            self.style_stack.pop();
+            popped_sticky = true;
+        }
         if self.style_stack.len() > 1 {
             self.style_stack.pop();
+            self.finish_gradients(self.style_stack.len());
+            Ok(())
+        } else if popped_sticky {
+            // e.g. `<red/>text</red>`: closing the self-closing tag just ends its style
             Ok(())
         } else {
-            Err(MiniMessageError("Unbalanced closing tag".to_string()))
+            Err(MiniMessageError::new("Unbalanced closing tag"))
         }
     }
 
-    fn reset_style(&mut self) -> Result<(), MiniMessageError> {
-        while self.style_stack.len() > 1 {
-            self.style_stack.pop();
+    /// Removes the frame at `index`, e.g. `<red>` when `</red>` closes it.
+    ///
+    /// Sticky frames pushed directly inside it end with it. Tags opened inside it that are
+    /// still open stay open: they keep the properties they set themselves, and stop
+    /// inheriting the ones of the closed tag. So in `<red><bold>x</red>y</bold>`, `y` is bold
+    /// but not red.
+    fn close_frame(&mut self, index: usize) {
+        // Gradients color their text when they close, so they close with the tags around them
+        if self.gradients.iter().any(|span| span.depth >= index) {
+            self.style_stack.truncate(index);
+            self.finish_gradients(index);
+            return;
         }
-        Ok(())
-    }
 
-    fn current_style(&self) -> &Style {
-        // SAFETY: This is safe because we always have at least one style
-        self.style_stack.last().unwrap()
+        let mut above = self.style_stack.split_off(index).into_iter();
+        let Some(closed) = above.next() else {
+            return;
+        };
+        let mut old_parent = closed.style;
+        let mut new_parent = self.current_style().clone();
+        let mut inside_open_tag = false;
+        for mut frame in above {
+            inside_open_tag |= !frame.sticky;
+            let old_style = frame.style;
+            if inside_open_tag {
+                frame.style = rebase_style(&old_style, &old_parent, &new_parent);
+                new_parent = frame.style.clone();
+                self.style_stack.push(frame);
+            }
+            old_parent = old_style;
+        }
     }
 
-    fn collect_decorations(&self) -> HashMap<TextDecoration, Option<bool>> {
-        let style = self.current_style();
-        let mut decorations = HashMap::new();
-        if let Some(bold) = style.bold {
-            decorations.insert(TextDecoration::Bold, Some(bold));
-        }
-        if let Some(italic) = style.italic {
-            decorations.insert(TextDecoration::Italic, Some(italic));
-        }
-        if let Some(underlined) = style.underlined {
-            decorations.insert(TextDecoration::Underlined, Some(underlined));
-        }
-        if let Some(strikethrough) = style.strikethrough {
-            decorations.insert(TextDecoration::Strikethrough, Some(strikethrough));
-        }
-        if let Some(obfuscated) = style.obfuscated {
-            decorations.insert(TextDecoration::Obfuscated, Some(obfuscated));
+    fn reset_style(&mut self) -> Result<(), MiniMessageError> {
+        while self.style_stack.len() > 1 {
+            self.style_stack.pop();
         }
-        decorations
+        self.finish_gradients(self.style_stack.len());
+        Ok(())
     }
 
-    fn starts_with(&self, c: char) -> bool {
-        self.input[self.position..].starts_with(c)
-    }
+    /// Colors the text of every open gradient that started at style stack length `depth` or
+    /// deeper, splitting it into one component per character.
+    ///
+    /// The gradient is spread evenly over all of its characters, and the phase shifts each
+    /// character's position along it, wrapping around. Text inside a nested gradient keeps
+    /// the nested gradient's colors, and text inside a color tag keeps that color.
+    fn finish_gradients(&mut self, depth: usize) {
+        while let Some(span) = self.gradients.pop_if(|span| span.depth >= depth) {
+            let is_nested = |index: usize| span.nested.iter().any(|range| range.contains(&index));
+            let text_of = |part: &Component| part.as_object().and_then(|obj| obj.text.clone());
+
+            let parts = self.component_parts.split_off(span.start);
+            let total: usize = parts
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !is_nested(span.start + index))
+                .filter_map(|(_, part)| text_of(part))
+                .map(|text| text.chars().count())
+                .sum();
+
+            let mut position = 0;
+            for (index, part) in parts.into_iter().enumerate() {
+                let (Component::Object(obj), Some(text)) = (&part, text_of(&part)) else {
+                    self.component_parts.push(part);
+                    continue;
+                };
+                if is_nested(span.start + index) {
+                    self.component_parts.push(part);
+                    continue;
+                }
+                for c in text.chars() {
+                    let mut t = if total > 1 {
+                        position as f32 / (total - 1) as f32
+                    } else {
+                        0.0
+                    };
+                    if span.phase != 0.0 {
+                        t = (t + span.phase).rem_euclid(1.0);
+                    }
+                    position += 1;
 
-    fn current_char(&self) -> char {
-        self.input[self.position..].chars().next().unwrap_or('\0')
-    }
+                    let mut obj = obj.clone();
+                    obj.text = Some(c.to_string());
+                    obj.color = Some(gradient_color(&span.stops, t));
+                    self.component_parts.push(Component::Object(obj));
+                }
+            }
 
-    fn skip_whitespace(&mut self) {
-        while self.position < self.input.len() {
-            if !self.input[self.position..].starts_with(char::is_whitespace) {
-                break;
+            if let Some(parent) = self.gradients.last_mut() {
+                parent.nested.push(span.start..self.component_parts.len());
             }
-            self.position += 1;
         }
     }
 
-    fn expect(&mut self, c: char) -> Result<(), MiniMessageError> {
-        if self.position < self.input.len() && self.current_char() == c {
-            self.position += 1;
-            Ok(())
-        } else {
-            Err(MiniMessageError(format!("Expected '{c}'")))
-        }
+    fn current_style(&self) -> &Style {
+        // The base style is never popped, so the fallback is only there to avoid a panic path
+        self.style_stack
+            .last()
+            .map_or(&EMPTY_STYLE, |frame| &frame.style)
     }
 }
 
@@ -483,13 +1672,17 @@ impl<'a> Parser<'a> {
 struct Serializer {
     output: String,
     current_style: Style,
+    newline_as_tag: bool,
+    escape_legacy: bool,
 }
 
 impl Serializer {
-    fn new() -> Self {
+    fn new(config: &MiniMessageConfig) -> Self {
         Self {
             output: String::new(),
             current_style: Style::default(),
+            newline_as_tag: config.newline_as_tag,
+            escape_legacy: config.parse_legacy_colors,
         }
     }
 
@@ -501,28 +1694,31 @@ impl Serializer {
     fn serialize_component(&mut self, component: &Component) -> Result<(), MiniMessageError> {
         match component {
             Component::String(s) => self.serialize_text(s),
-            Component::Array(components) => {
-                let base_style = self.current_style.clone();
-                for comp in components {
-                    // Reset to base style before each component
-                    self.current_style = base_style.clone();
-                    self.serialize_component(comp)?;
-                }
-                Ok(())
-            }
-            Component::Object(obj) => self.serialize_object(obj),
+            // The first element of an array is the parent of the others, so its style wraps them
+            Component::Array(components) => match components.split_first() {
+                Some((Component::Object(first), rest)) => self.serialize_object(first, rest),
+                Some((first, rest)) => self.serialize_object(&first.clone().into_object(), rest),
+                None => Ok(()),
+            },
+            Component::Object(obj) => self.serialize_object(obj, &[]),
         }
     }
 
-    fn serialize_object(&mut self, obj: &ComponentObject) -> Result<(), MiniMessageError> {
+    /// Serializes `obj` and its children, followed by `siblings` that inherit its style
+    fn serialize_object(
+        &mut self,
+        obj: &ComponentObject,
+        siblings: &[Component],
+    ) -> Result<(), MiniMessageError> {
         // Save current style to compare changes
         let prev_style = self.current_style.clone();
+        let mut style = obj.style();
+        style.inherit(&prev_style);
 
-        // Apply style changes
         let mut style_changes = Vec::new();
 
-        if let Some(color) = &obj.color
-            && Some(color) != prev_style.color.as_ref()
+        if style.color != prev_style.color
+            && let Some(color) = &style.color
         {
             if let Some(named) = color.to_named() {
                 style_changes.push(named.to_string());
@@ -531,74 +1727,196 @@ impl Serializer {
             }
         }
 
-        if obj.bold != prev_style.bold && obj.bold == Some(true) {
-            style_changes.push("bold".to_string());
+        let decorations = [
+            ("bold", style.bold, prev_style.bold),
+            ("italic", style.italic, prev_style.italic),
+            ("underlined", style.underlined, prev_style.underlined),
+            (
+                "strikethrough",
+                style.strikethrough,
+                prev_style.strikethrough,
+            ),
+            ("obfuscated", style.obfuscated, prev_style.obfuscated),
+        ];
+        for (name, state, prev) in decorations {
+            if state != prev {
+                match state {
+                    Some(true) => style_changes.push(name.to_string()),
+                    Some(false) => style_changes.push(format!("!{name}")),
+                    None => {}
+                }
+            }
         }
 
-        if obj.italic != prev_style.italic && obj.italic == Some(true) {
-            style_changes.push("italic".to_string());
+        if style.shadow_color != prev_style.shadow_color
+            && let Some(shadow) = &style.shadow_color
+        {
+            let [r, g, b, a] = shadow.to_rgba();
+            style_changes.push(format!("shadow:#{r:02x}{g:02x}{b:02x}{a:02x}"));
         }
 
-        if obj.underlined != prev_style.underlined && obj.underlined == Some(true) {
-            style_changes.push("underlined".to_string());
+        if style.click_event != prev_style.click_event
+            && let Some(event) = &style.click_event
+        {
+            let value = match event {
+                ClickEvent::OpenUrl { url } => Cow::Borrowed(url),
+                ClickEvent::OpenFile { path } => Cow::Borrowed(path),
+                ClickEvent::RunCommand { command } | ClickEvent::SuggestCommand { command } => {
+                    Cow::Borrowed(command)
+                }
+                ClickEvent::ChangePage { page } => Cow::Owned(page.to_string()),
+                ClickEvent::CopyToClipboard { value } => Cow::Borrowed(value),
+                // Dialogs and custom payloads hold arbitrary data, which a tag argument can't
+                ClickEvent::ShowDialog { .. }
+                | ClickEvent::Custom { .. }
+                | ClickEvent::Other(_) => {
+                    return Err(MiniMessageError::new(format!(
+                        "Click action '{}' can't be written as a MiniMessage tag",
+                        event.action()
+                    )));
+                }
+            };
+            style_changes.push(format!(
+                "click:{}:{}",
+                event.action(),
+                quote_arg(&value, true)
+            ));
         }
 
-        if obj.strikethrough != prev_style.strikethrough && obj.strikethrough == Some(true) {
-            style_changes.push("strikethrough".to_string());
+        if style.hover_event != prev_style.hover_event
+            && let Some(HoverEvent::ShowText { value }) = &style.hover_event
+        {
+            // Hover text is parsed on its own, so it doesn't inherit the current style
+            let text = Serializer {
+                output: String::new(),
+                current_style: Style::default(),
+                newline_as_tag: self.newline_as_tag,
+                escape_legacy: self.escape_legacy,
+            }
+            .serialize(value)?;
+            style_changes.push(format!("hover:show_text:{}", quote_arg(&text, true)));
         }
 
-        if obj.obfuscated != prev_style.obfuscated && obj.obfuscated == Some(true) {
-            style_changes.push("obfuscated".to_string());
+        if style.insertion != prev_style.insertion
+            && let Some(insertion) = &style.insertion
+        {
+            style_changes.push(format!("insert:{}", quote_arg(insertion, false)));
         }
 
-        // Apply style changes
         for change in &style_changes {
             self.output.push_str(&format!("<{change}>"));
         }
 
-        // Update current style
-        self.current_style = Style {
-            color: obj.color.clone(),
-            bold: obj.bold,
-            italic: obj.italic,
-            underlined: obj.underlined,
-            strikethrough: obj.strikethrough,
-            obfuscated: obj.obfuscated,
-            ..self.current_style.clone()
-        };
-
-        // Serialize text content
-        if let Some(text) = &obj.text {
-            self.serialize_text(text)?;
-        }
+        self.current_style = style;
 
-        // Serialize children
-        if let Some(extra) = &obj.extra {
-            for comp in extra {
-                self.serialize_component(comp)?;
-            }
+        self.serialize_content(obj)?;
+        for comp in obj.extra.iter().flatten().chain(siblings) {
+            self.serialize_component(comp)?;
         }
 
-        // Close style changes
+        // Close style changes; closing tags take no arguments
         for change in style_changes.iter().rev() {
-            self.output.push_str(&format!("</{change}>"));
+            let name = change.split(':').next().unwrap_or(change);
+            self.output.push_str(&format!("</{name}>"));
         }
 
-        // Restore previous style
         self.current_style = prev_style;
 
         Ok(())
     }
 
+    /// Writes the content of `obj`: its text, or a tag for the other content types. See
+    /// [`Parser::content_from_args`] for the tags.
+    ///
+    /// A fixed score `value` has no MiniMessage form and is left out.
+    fn serialize_content(&mut self, obj: &ComponentObject) -> Result<(), MiniMessageError> {
+        if let Some(text) = &obj.text {
+            return self.serialize_text(text);
+        }
+
+        let mut args = Vec::new();
+        let tag = if let Some(key) = &obj.translate {
+            args.push(key.clone());
+            args.extend(obj.fallback.clone());
+            for arg in obj.with.iter().flatten() {
+                args.push(self.serialize_nested(arg)?);
+            }
+            if obj.fallback.is_some() {
+                "lang_or"
+            } else {
+                "lang"
+            }
+        } else if let Some(score) = &obj.score {
+            args.extend([score.name.clone(), score.objective.clone()]);
+            "score"
+        } else if let Some(selector) = &obj.selector {
+            args.push(selector.clone());
+            if let Some(separator) = &obj.separator {
+                args.push(self.serialize_nested(separator)?);
+            }
+            "selector"
+        } else if let Some(keybind) = &obj.keybind {
+            args.push(keybind.clone());
+            "key"
+        } else if let Some(path) = &obj.nbt {
+            let sources = [
+                (NbtSource::Block, "block", &obj.block),
+                (NbtSource::Entity, "entity", &obj.entity),
+                (NbtSource::Storage, "storage", &obj.storage),
+            ];
+            let Some((_, source, Some(id))) = sources
+                .into_iter()
+                .find(|(source, _, id)| obj.source.map_or(id.is_some(), |s| s == *source))
+            else {
+                return Err(MiniMessageError::new(format!(
+                    "NBT component '{path}' has no block, entity or storage to read from"
+                )));
+            };
+            args.extend([source.to_string(), id.clone(), path.clone()]);
+            if let Some(separator) = &obj.separator {
+                args.push(self.serialize_nested(separator)?);
+            }
+            if obj.interpret == Some(true) {
+                args.push("interpret".to_string());
+            }
+            "nbt"
+        } else {
+            return Ok(());
+        };
+
+        self.output.push('<');
+        self.output.push_str(tag);
+        for arg in &args {
+            self.output.push(':');
+            self.output.push_str(&quote_arg(arg, false));
+        }
+        self.output.push('>');
+        Ok(())
+    }
+
+    /// Serializes a component used as a tag argument, inheriting the current style
+    fn serialize_nested(&self, component: &Component) -> Result<String, MiniMessageError> {
+        Serializer {
+            output: String::new(),
+            current_style: self.current_style.clone(),
+            newline_as_tag: self.newline_as_tag,
+            escape_legacy: self.escape_legacy,
+        }
+        .serialize(component)
+    }
+
+    /// Writes text, escaping exactly the characters the parser would treat specially under the
+    /// same configuration
     fn serialize_text(&mut self, text: &str) -> Result<(), MiniMessageError> {
-        // Escape special characters
-        for c in text.chars() {
-            match c {
-                '<' => self.output.push_str("&lt;"),
-                '>' => self.output.push_str("&gt;"),
-                '&' => self.output.push_str("&amp;"),
-                _ => self.output.push(c),
+        if !self.newline_as_tag {
+            push_escaped(&mut self.output, text, self.escape_legacy);
+            return Ok(());
+        }
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.output.push_str("<newline>");
             }
+            push_escaped(&mut self.output, line, self.escape_legacy);
         }
         Ok(())
     }
@@ -608,6 +1926,7 @@ impl Serializer {
 mod tests {
     use super::*;
     use crate::{Component, NamedColor};
+    use std::collections::HashMap;
 
     #[test]
     fn test_parse_simple() {
@@ -616,9 +1935,9 @@ mod tests {
 
         if let Component::Array(parts) = comp {
             assert_eq!(parts.len(), 3);
-            assert_eq!(parts[0].get_plain_text().unwrap(), "Hello ");
-            assert_eq!(parts[1].get_plain_text().unwrap(), "world");
-            assert_eq!(parts[2].get_plain_text().unwrap(), "!");
+            assert_eq!(parts[0], "Hello ");
+            assert_eq!(parts[1], "world");
+            assert_eq!(parts[2], "!");
         } else {
             panic!("Expected array component");
         }
@@ -634,16 +1953,14 @@ mod tests {
         // Verify hover event exists and contains a red text component
         if let Component::Object(obj) = &comp
             && let Some(children) = &obj.extra
+            && let Component::Object(hover_obj) = &children[1]
+            && let Some(hover_event) = &hover_obj.hover_event
         {
-            if let Component::Object(hover_obj) = &children[1]
-                && let Some(hover_event) = &hover_obj.hover_event
-            {
-                match hover_event {
-                    HoverEvent::ShowText { value } => {
-                        assert_eq!(value.get_plain_text().unwrap(), "Action!");
-                    }
-                    _ => panic!("Expected show_text hover event"),
+            match hover_event {
+                HoverEvent::ShowText { value } => {
+                    assert_eq!(value.get_plain_text().unwrap(), "Action!");
                 }
+                _ => panic!("Expected show_text hover event"),
             }
         }
     }
@@ -659,6 +1976,110 @@ mod tests {
         assert_eq!(result, "<yellow>Hello <red>world</red></yellow>");
     }
 
+    /// Runs of text with their effective style, merging neighbours styled the same way
+    fn styled_text(comp: &Component) -> Vec<(String, Style)> {
+        let mut merged: Vec<(String, Style)> = Vec::new();
+        for (text, style) in comp.styled_runs() {
+            match merged.last_mut() {
+                Some((last, last_style)) if *last_style == style => last.push_str(text),
+                _ if text.is_empty() => {}
+                _ => merged.push((text.to_string(), style)),
+            }
+        }
+        merged
+    }
+
+    fn assert_round_trip(comp: &Component) {
+        let serialized = MiniMessage::to_string(comp).unwrap();
+        let reparsed = MiniMessage::new().parse(&serialized).unwrap();
+        assert_eq!(
+            styled_text(&reparsed),
+            styled_text(comp),
+            "{serialized} lost formatting"
+        );
+    }
+
+    #[test]
+    fn test_serialize_round_trips() {
+        let style = |color: Option<Color>, bold, italic, underlined| Style {
+            color,
+            bold,
+            italic,
+            underlined,
+            ..Default::default()
+        };
+        let red = || Some(Color::Named(NamedColor::Red));
+        let hex = || Some(Color::Hex("#123456".into()));
+        let cases = [
+            (
+                "<red>a<bold>b</bold>c</red>d",
+                vec![
+                    ("a", style(red(), None, None, None)),
+                    ("b", style(red(), Some(true), None, None)),
+                    ("c", style(red(), None, None, None)),
+                    ("d", Style::default()),
+                ],
+            ),
+            (
+                "<color:#123456>hex <green>named</green> back</color>",
+                vec![
+                    ("hex ", style(hex(), None, None, None)),
+                    (
+                        "named",
+                        style(Some(Color::Named(NamedColor::Green)), None, None, None),
+                    ),
+                    (" back", style(hex(), None, None, None)),
+                ],
+            ),
+            (
+                "<bold>a<!bold>b</!bold>c</bold>",
+                vec![
+                    ("a", style(None, Some(true), None, None)),
+                    ("b", style(None, Some(false), None, None)),
+                    ("c", style(None, Some(true), None, None)),
+                ],
+            ),
+            (
+                "<italic><u>a<red>b</red></u>c</italic>",
+                vec![
+                    ("a", style(None, None, Some(true), Some(true))),
+                    ("b", style(red(), None, Some(true), Some(true))),
+                    ("c", style(None, None, Some(true), None)),
+                ],
+            ),
+            ("plain", vec![("plain", Style::default())]),
+        ];
+        for (input, expected) in cases {
+            let expected: Vec<_> = expected
+                .into_iter()
+                .map(|(text, style)| (text.to_string(), style))
+                .collect();
+            let parsed = MiniMessage::new().parse(input).unwrap();
+            assert_eq!(styled_text(&parsed), expected, "{input} parsed wrong");
+            assert_round_trip(&parsed);
+        }
+
+        // Array elements after the first inherit its style
+        assert_round_trip(&Component::Array(vec![
+            Component::text("a").color(red()),
+            Component::from("b"),
+            Component::text("c").decoration(TextDecoration::Italic, Some(true)),
+        ]));
+        // Inherited color survives a child that only sets a decoration
+        assert_round_trip(
+            &Component::text("a")
+                .color(red())
+                .append(Component::text("b").decoration(TextDecoration::Bold, Some(true)))
+                .append(Component::text("c").color(red())),
+        );
+        // An explicit `false` under a decorated parent
+        assert_round_trip(
+            &Component::text("a")
+                .decoration(TextDecoration::Bold, Some(true))
+                .append(Component::text("b").decoration(TextDecoration::Bold, Some(false))),
+        );
+    }
+
     // TODO: comprehensive tests would involve traversing the parsed MiniMessage's tree
     #[test]
     fn test_readme_example_basic_red_text() {
@@ -689,4 +2110,944 @@ mod tests {
         let comp = mm.parse("<hover:show_text:\"<red>Hover Text</red>\"><click:open_url:\"https://example.com\">Clickable Link</click></hover>").unwrap();
         assert_eq!(comp.to_plain_text(), "Clickable Link");
     }
+
+    #[test]
+    fn test_tokenizer_mixed() {
+        let tokens: Vec<_> =
+            MiniMessageTokenizer::new("Hi <click:run_command:'/say é'><b/>there</click>")
+                .collect::<Result<_, _>>()
+                .unwrap();
+        assert_eq!(
+            tokens,
+            [
+                Token::Text("Hi "),
+                Token::OpenTag {
+                    name: "click".to_string(),
+                    args: vec!["run_command".to_string(), "/say é".to_string()],
+                },
+                Token::SelfClosing {
+                    name: "b".to_string(),
+                    args: vec![],
+                },
+                Token::Text("there"),
+                Token::CloseTag("click".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenizer_stops_after_error() {
+        let mut tokens = MiniMessageTokenizer::new("ok <b:'oops");
+        assert_eq!(tokens.next(), Some(Ok(Token::Text("ok "))));
+        assert!(matches!(tokens.next(), Some(Err(_))));
+        assert_eq!(tokens.next(), None);
+    }
+
+    #[test]
+    fn test_lone_angle_brackets_are_text() {
+        let mm = MiniMessage::new();
+        assert_eq!(
+            mm.parse("5 < 10 > 3").unwrap(),
+            Component::text("5 < 10 > 3")
+        );
+        assert_eq!(mm.parse("<3 you").unwrap(), Component::text("<3 you"));
+        assert_eq!(
+            mm.parse("a <= b, x<").unwrap(),
+            Component::text("a <= b, x<")
+        );
+
+        // A `<` before a letter still starts a tag
+        let comp = mm.parse("1 < <red>2</red>").unwrap();
+        assert_eq!(comp, "1 < 2");
+        assert_eq!(
+            comp.to_spans()[1].style.color,
+            Some(Color::Named(NamedColor::Red))
+        );
+
+        let tokens: Vec<_> = MiniMessageTokenizer::new("a < b<red>")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(tokens[0], Token::Text("a < b"));
+    }
+
+    #[test]
+    fn test_backslash_escapes() {
+        let mm = MiniMessage::new();
+        assert_eq!(
+            mm.parse(r"\<red>x\</red>").unwrap(),
+            Component::text("<red>x</red>")
+        );
+
+        // An escaped backslash doesn't escape the tag after it
+        let comp = mm.parse(r"a\\<red>b</red>").unwrap();
+        assert_eq!(comp, r"a\b");
+        assert_eq!(
+            comp.to_spans()[1].style.color,
+            Some(Color::Named(NamedColor::Red))
+        );
+
+        // `&` is only special, and only escapable, with legacy codes on
+        assert_eq!(mm.parse(r"\&c").unwrap(), Component::text(r"\&c"));
+        let legacy = MiniMessage::with_config(MiniMessageConfig {
+            parse_legacy_colors: true,
+            ..Default::default()
+        });
+        assert_eq!(legacy.parse(r"\&cx").unwrap(), Component::text("&cx"));
+        assert_eq!(
+            legacy.serialize(&Component::text("<red> & more")).unwrap(),
+            r"\<red> \& more"
+        );
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let built = MiniMessageConfig::builder()
+            .strict(true)
+            .parse_legacy_colors(true)
+            .unknown_tags(UnknownTagPolicy::Strip)
+            .newline_as_tag(true)
+            .tag_alias("Primary", "gold")
+            .build();
+        assert_eq!(
+            built,
+            MiniMessageConfig {
+                strict: true,
+                parse_legacy_colors: true,
+                unknown_tags: UnknownTagPolicy::Strip,
+                newline_as_tag: true,
+                tag_aliases: BTreeMap::from([("primary".to_string(), "gold".to_string())]),
+            }
+        );
+        assert_eq!(
+            MiniMessageConfig::builder().build(),
+            MiniMessageConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_tag_aliases() {
+        let mm = MiniMessage::with_config(
+            MiniMessageConfig::builder()
+                .tag_alias("primary", "gold")
+                .tag_alias("Accent", "color:#FFAA00")
+                .tag_alias("red", "blue")
+                .tag_alias("broken", "<")
+                .build(),
+        );
+        let colors = |input: &str| {
+            mm.parse(input)
+                .unwrap()
+                .to_spans()
+                .into_iter()
+                .map(|span| (span.text, span.style.color))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            colors("-<primary>a</primary>b<accent>c</accent>"),
+            vec![
+                ("-".to_string(), None),
+                ("a".to_string(), Some(Color::Named(NamedColor::Gold))),
+                ("b".to_string(), None),
+                ("c".to_string(), Some(Color::Hex("#FFAA00".into()))),
+            ]
+        );
+        // Aliases take precedence over built-in tags
+        assert_eq!(
+            colors("<red>x</red>"),
+            vec![("x".to_string(), Some(Color::Named(NamedColor::Blue)))]
+        );
+        assert!(mm.parse("<broken>x").is_err());
+    }
+
+    #[test]
+    fn test_parse_recovering_reports_every_error() {
+        let mm = MiniMessage::with_config(
+            MiniMessageConfig::builder()
+                .strict(true)
+                .unknown_tags(UnknownTagPolicy::Error)
+                .build(),
+        );
+        let input = "<red>a</blue><wat>b<click:nope:x>c</click> <b:'oops";
+        assert!(mm.parse(input).is_err());
+
+        let (component, errors) = mm.parse_recovering(input);
+        assert_eq!(component, "a</blue><wat>b<click:nope:x>c</click> <b:'oops");
+        let positions: Vec<_> = errors.iter().map(MiniMessageError::position).collect();
+        assert_eq!(positions, [Some(6), Some(13), Some(19), Some(34), Some(43)]);
+        assert_eq!(errors[1].message(), "Unknown tag 'wat'");
+
+        // The parts that parsed fine keep their style
+        assert_eq!(
+            component.to_spans()[0].style.color,
+            Some(Color::Named(NamedColor::Red))
+        );
+
+        let (clean, errors) = mm.parse_recovering("<red>fine</red>");
+        assert!(errors.is_empty());
+        assert_eq!(clean, mm.parse("<red>fine</red>").unwrap());
+    }
+
+    #[test]
+    fn test_gradient_stops_and_phase() {
+        let mm = MiniMessage::new();
+        let colors = |input: &str| -> Vec<(String, Option<Color>)> {
+            let Component::Array(parts) = mm.parse(input).unwrap() else {
+                panic!("expected an array");
+            };
+            // After the unstyled first part keeping the colors to themselves
+            parts[1..]
+                .iter()
+                .map(|part| {
+                    (
+                        part.to_plain_text().into_owned(),
+                        part.as_object().unwrap().color.clone(),
+                    )
+                })
+                .collect()
+        };
+        let hex = |hex: &str| Some(Color::Hex(hex.into()));
+
+        // Three named stops spread evenly over five characters
+        assert_eq!(
+            colors("<gradient:red:blue:green>ab<bold>c</bold>de</gradient>"),
+            [
+                ("a".into(), hex("#FF5555")),
+                ("b".into(), hex("#AA55AA")),
+                ("c".into(), hex("#5555FF")),
+                ("d".into(), hex("#55AAAA")),
+                ("e".into(), hex("#55FF55")),
+            ]
+        );
+
+        // The phase shifts every position, wrapping around; an unclosed gradient runs to the end
+        assert_eq!(
+            colors("<gradient:#000000:#FFFFFF:0.5>abc"),
+            [
+                ("a".into(), hex("#808080")),
+                ("b".into(), hex("#000000")),
+                ("c".into(), hex("#808080")),
+            ]
+        );
+
+        // A nested gradient keeps its own colors, and the outer one still spans all the text
+        assert_eq!(
+            colors("<gradient:#000000:#FFFFFF>a<gradient:red>b</gradient>c</gradient>"),
+            [
+                ("a".into(), hex("#000000")),
+                ("b".into(), Some(Color::Named(NamedColor::Red))),
+                ("c".into(), hex("#FFFFFF")),
+            ]
+        );
+
+        // Text with its own color keeps it, and the gradient spans the rest
+        assert_eq!(
+            colors("<gradient:red:blue>a<green>b</green>c</gradient>"),
+            [
+                ("a".into(), hex("#FF5555")),
+                ("b".into(), Some(Color::Named(NamedColor::Green))),
+                ("c".into(), hex("#5555FF")),
+            ]
+        );
+
+        // A single stop colors everything uniformly
+        let single = mm.parse("<gradient:gold>hi</gradient> there").unwrap();
+        assert_eq!(
+            single,
+            Component::Array(vec![
+                "".into(),
+                Component::text("h").color(Some(Color::Named(NamedColor::Gold))),
+                Component::text("i").color(Some(Color::Named(NamedColor::Gold))),
+                Component::text(" there"),
+            ])
+        );
+
+        // Empty text leaves nothing to color
+        assert_eq!(
+            mm.parse("<gradient:red:blue></gradient>").unwrap(),
+            Component::Array(vec![])
+        );
+
+        // Invalid stops or an out of range phase keep the tag as text
+        assert_eq!(
+            mm.parse("<gradient:red:nope>x</gradient>").unwrap(),
+            "<gradient:red:nope>x"
+        );
+        assert_eq!(
+            mm.parse("<gradient:red:blue:2>x</gradient>").unwrap(),
+            "<gradient:red:blue:2>x"
+        );
+    }
+
+    #[test]
+    fn test_out_of_order_close_tags() {
+        let mm = MiniMessage::new();
+        let red = Some(Color::Named(NamedColor::Red));
+        let bold = |text| Component::text(text).decoration(TextDecoration::Bold, Some(true));
+
+        // Closing `</red>` ends only the red, and `y` stays bold until `</bold>`
+        assert_eq!(
+            mm.parse("_<red><bold>x</red>y</bold>z").unwrap(),
+            Component::Array(vec![
+                Component::text("_"),
+                bold("x").color(red.clone()),
+                bold("y"),
+                Component::text("z"),
+            ])
+        );
+
+        // Properties set by the inner tag win even when the outer tag set them too
+        let comp = mm
+            .parse("_<bold><red><!bold><u>x</bold>y</u>z</red>")
+            .unwrap();
+        assert_eq!(
+            comp.get_children()[2],
+            Component::text("y")
+                .color(red.clone())
+                .decoration(TextDecoration::Bold, Some(false))
+                .decoration(TextDecoration::Underlined, Some(true))
+        );
+        assert_eq!(
+            comp.get_children()[3],
+            Component::text("z")
+                .color(red.clone())
+                .decoration(TextDecoration::Bold, Some(false))
+        );
+
+        // A self-closing tag inside the closed tag ends with it
+        assert_eq!(
+            mm.parse("_<red><italic/>x</red>y").unwrap().get_children()[2],
+            Component::text("y")
+        );
+
+        // Repeated tags close the innermost one first
+        let comp = mm.parse("_<bold><bold>x</bold>y</bold>z").unwrap();
+        assert_eq!(comp.get_children()[2], bold("y"));
+        assert_eq!(comp.get_children()[3], Component::text("z"));
+    }
+
+    #[test]
+    fn test_serialize_quotes_only_when_needed() {
+        let mm = MiniMessage::new();
+        let cases = [
+            (
+                Component::text("spawn").click_run("/spawn"),
+                "<click:run_command:/spawn>spawn</click>",
+            ),
+            (
+                Component::text("docs").click_url("https://example.com/search?q=a&page=2"),
+                "<click:open_url:'https://example.com/search?q=a&page=2'>docs</click>",
+            ),
+            (
+                Component::text("tp").click_suggest("/tp @s 0 64 0"),
+                "<click:suggest_command:'/tp @s 0 64 0'>tp</click>",
+            ),
+            (
+                Component::text("page").click_event(Some(ClickEvent::ChangePage { page: 3 })),
+                "<click:change_page:3>page</click>",
+            ),
+            (
+                Component::text("tip").hover_text(Component::text("Hello")),
+                "<hover:show_text:Hello>tip</hover>",
+            ),
+            (
+                Component::text("tip").hover_text(
+                    Component::text("it's red").color(Some(Color::Named(NamedColor::Red))),
+                ),
+                r"<hover:show_text:'<red>it\'s red</red>'>tip</hover>",
+            ),
+            (
+                Component::text("name").insertion(Some("Steve".into())),
+                "<insert:Steve>name</insert>",
+            ),
+            (
+                Component::text("path").insertion(Some("a/b".into())),
+                "<insert:'a/b'>path</insert>",
+            ),
+        ];
+        for (component, expected) in cases {
+            let serialized = mm.serialize(&component).unwrap();
+            assert_eq!(serialized, expected);
+            assert_eq!(mm.parse(&serialized).unwrap(), component);
+        }
+    }
+
+    #[test]
+    fn test_array_hover_text_round_trip() {
+        let hover_of = |comp: &Component| match comp.as_object().and_then(|o| o.hover_event.clone())
+        {
+            Some(HoverEvent::ShowText { value }) => value,
+            other => panic!("expected show_text, got {other:?}"),
+        };
+        let runs = |comp: &Component| {
+            comp.iter_text()
+                .map(|(text, style)| (text.to_string(), style.color))
+                .collect::<Vec<_>>()
+        };
+        let red = Some(Color::Named(NamedColor::Red));
+
+        // A styled first part must not pass its color on to the parts after it
+        let parsed = MiniMessage::new()
+            .parse("<hover:show_text:'<red>a</red>b'>x</hover>")
+            .unwrap();
+        let value = hover_of(&parsed);
+        assert_eq!(
+            runs(&value),
+            vec![("a".to_string(), red.clone()), ("b".to_string(), None)]
+        );
+
+        // Array hover text coming from JSON serializes and parses back to the same text
+        let json = r#"{"text":"x","hover_event":{"action":"show_text","value":["",{"text":"a","color":"red"},"b"]}}"#;
+        let comp: Component = serde_json::from_str(json).unwrap();
+        let text = MiniMessage::to_string(&comp).unwrap();
+        let reparsed = MiniMessage::new().parse(&text).unwrap();
+        assert_eq!(runs(&hover_of(&reparsed)), runs(&hover_of(&comp)));
+        assert_eq!(reparsed.to_plain_text(), "x");
+    }
+
+    #[test]
+    fn test_invalid_color_argument() {
+        let mm = MiniMessage::new();
+        let comp = mm.parse("<color:#12>x</color>").unwrap();
+        assert_eq!(comp.to_plain_text(), "<color:#12>x");
+
+        // The closing tag matches the rejected one and leaves the enclosing color alone
+        let comp = mm.parse("<red><color:bogus>x</color>y</red>").unwrap();
+        assert_eq!(comp.to_plain_text(), "<color:bogus>xy");
+        assert!(
+            comp.iter_text()
+                .filter(|(text, _)| !text.is_empty())
+                .all(|(_, style)| style.color == Some(Color::Named(NamedColor::Red)))
+        );
+
+        let strict = MiniMessage::with_config(MiniMessageConfig {
+            strict: true,
+            ..Default::default()
+        });
+        let err = strict.parse("<color:#12>x</color>").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "MiniMessage error: Invalid color in '<color:#12>'"
+        );
+    }
+
+    #[test]
+    fn test_styles_stay_with_their_parts() {
+        let mm = MiniMessage::new();
+        let red = Some(Color::Named(NamedColor::Red));
+        let colors = |input: &str| {
+            mm.parse(input)
+                .unwrap()
+                .iter_text()
+                .filter(|(text, _)| !text.is_empty())
+                .map(|(text, style)| (text.to_string(), style.color))
+                .collect::<Vec<_>>()
+        };
+
+        // Parts after a styled first part don't inherit its style
+        assert_eq!(
+            colors("<red>a</red>b"),
+            [("a".to_string(), red.clone()), ("b".to_string(), None)]
+        );
+        assert_eq!(
+            colors("<red>a<reset>b"),
+            [("a".to_string(), red), ("b".to_string(), None)]
+        );
+        let hover = mm.parse("<hover:show_text:'x'>h</hover>y").unwrap();
+        assert_eq!(
+            hover
+                .iter_text()
+                .filter(|(_, style)| style.hover_event.is_some())
+                .map(|(text, _)| text)
+                .collect::<Vec<_>>(),
+            ["h"]
+        );
+    }
+
+    #[test]
+    fn test_serialize_rejects_unsupported_clicks() {
+        let clicks = [
+            Component::text("a").click_show_dialog("minecraft:server_links"),
+            Component::text("b").click_custom("myplugin:vote", None),
+            serde_json::from_str(r#"{"text":"c","click_event":{"action":"launch_rocket"}}"#)
+                .unwrap(),
+        ];
+        for comp in clicks {
+            assert!(MiniMessage::to_string(&comp).is_err());
+        }
+    }
+
+    fn click_of(comp: &Component) -> Option<ClickEvent> {
+        comp.find_nodes(|obj| obj.click_event.is_some())
+            .first()
+            .and_then(|obj| obj.click_event.clone())
+    }
+
+    #[test]
+    fn test_click_actions() {
+        let mm = MiniMessage::new();
+        let cases = [
+            (
+                "<click:run_command:'/help'>x</click>",
+                ClickEvent::RunCommand {
+                    command: "/help".to_string(),
+                },
+            ),
+            (
+                "<click:suggest_command:'/msg '>x</click>",
+                ClickEvent::SuggestCommand {
+                    command: "/msg ".to_string(),
+                },
+            ),
+            (
+                "<click:open_url:'https://example.com'>x</click>",
+                ClickEvent::OpenUrl {
+                    url: "https://example.com".to_string(),
+                },
+            ),
+            (
+                "<click:open_file:'logs/latest.log'>x</click>",
+                ClickEvent::OpenFile {
+                    path: "logs/latest.log".to_string(),
+                },
+            ),
+            (
+                "<click:change_page:3>x</click>",
+                ClickEvent::ChangePage { page: 3 },
+            ),
+            (
+                "<click:copy_to_clipboard:hello>x</click>",
+                ClickEvent::CopyToClipboard {
+                    value: "hello".to_string(),
+                },
+            ),
+        ];
+        for (input, expected) in cases {
+            let comp = mm.parse(input).unwrap();
+            assert_eq!(click_of(&comp), Some(expected), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_unquoted_values_run_to_tag_end() {
+        let mm = MiniMessage::new();
+        let comp = mm
+            .parse("<click:open_url:https://example.com:8080/a?b=c>x</click>")
+            .unwrap();
+        assert_eq!(
+            click_of(&comp),
+            Some(ClickEvent::OpenUrl {
+                url: "https://example.com:8080/a?b=c".to_string(),
+            })
+        );
+
+        let comp = mm.parse("<click:run_command:/tp @s 0 64 0>x").unwrap();
+        assert_eq!(
+            click_of(&comp),
+            Some(ClickEvent::RunCommand {
+                command: "/tp @s 0 64 0".to_string(),
+            })
+        );
+
+        // A trailing slash still marks a self-closing tag
+        let comp = mm.parse("a<click:copy_to_clipboard:x:y/>b").unwrap();
+        assert_eq!(
+            click_of(&comp.get_children()[1]),
+            Some(ClickEvent::CopyToClipboard {
+                value: "x:y".to_string(),
+            })
+        );
+
+        let comp = mm.parse("<hover:show_text:Time: 12:00>x</hover>").unwrap();
+        assert!(matches!(
+            &comp,
+            Component::Object(obj) if matches!(
+                &obj.hover_event,
+                Some(HoverEvent::ShowText { value }) if value.to_plain_text() == "Time: 12:00"
+            )
+        ));
+    }
+
+    #[test]
+    fn test_hover_actions() {
+        let mm = MiniMessage::new();
+        let comp = mm
+            .parse("<hover:show_item:'minecraft:stone':5>x</hover>")
+            .unwrap();
+        assert!(matches!(
+            comp.find_nodes(|_| true)[0].hover_event,
+            Some(HoverEvent::ShowItem { ref id, count: Some(5), .. }) if id == "minecraft:stone"
+        ));
+
+        let comp = mm
+            .parse("<hover:show_entity:'minecraft:pig':'0-0-0-0-1':'<red>Bob'>x</hover>")
+            .unwrap();
+        match &comp.find_nodes(|_| true)[0].hover_event {
+            Some(HoverEvent::ShowEntity {
+                name: Some(name),
+                id,
+                uuid: UuidRepr::String(uuid),
+            }) => {
+                assert_eq!(id, "minecraft:pig");
+                assert_eq!(uuid, "0-0-0-0-1");
+                assert_eq!(name.to_plain_text(), "Bob");
+            }
+            other => panic!("Expected show_entity hover event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_event_actions() {
+        let lenient = MiniMessage::new();
+        let comp = lenient.parse("<click:explode:now>boom</click>").unwrap();
+        assert_eq!(comp.to_plain_text(), "<click:explode:now>boom");
+        assert_eq!(click_of(&comp), None);
+
+        let comp = lenient.parse("<hover:show_nothing:x>hi</hover>").unwrap();
+        assert_eq!(comp.to_plain_text(), "<hover:show_nothing:x>hi");
+
+        let strict = MiniMessage::with_config(MiniMessageConfig {
+            strict: true,
+            ..Default::default()
+        });
+        assert!(strict.parse("<click:explode:now>boom</click>").is_err());
+        assert!(strict.parse("<click:change_page:two>x</click>").is_err());
+        assert!(strict.parse("<hover:show_nothing:x>hi</hover>").is_err());
+    }
+
+    #[test]
+    fn test_legacy_to_minimessage() {
+        assert_eq!(
+            legacy_to_minimessage("§cRed §lbold§r plain & stuff"),
+            "<red>Red <bold>bold<reset> plain & stuff"
+        );
+        assert_eq!(
+            legacy_to_minimessage("&#ff8800orange §x§1§2§3§4§5§6hex"),
+            "<color:#ff8800>orange <color:#123456>hex"
+        );
+    }
+
+    #[test]
+    fn test_legacy_round_trip() {
+        let legacy = "&6Gold &lbold &cred&r plain";
+        let from_legacy = MiniMessage::from_legacy(legacy).unwrap();
+        let expected = Component::Array(vec![
+            "".into(),
+            Component::text("Gold ").color(Some(Color::Named(NamedColor::Gold))),
+            Component::text("bold ")
+                .color(Some(Color::Named(NamedColor::Gold)))
+                .decoration(TextDecoration::Bold, Some(true)),
+            Component::text("red").color(Some(Color::Named(NamedColor::Red))),
+            Component::text(" plain"),
+        ]);
+        assert_eq!(from_legacy, expected);
+
+        // Parsing the legacy text directly gives the same result
+        let direct = MiniMessage::with_config(MiniMessageConfig {
+            parse_legacy_colors: true,
+            ..Default::default()
+        })
+        .parse(legacy)
+        .unwrap();
+        assert_eq!(direct, expected);
+    }
+
+    fn parse_with_policy(
+        input: &str,
+        policy: UnknownTagPolicy,
+    ) -> Result<Component, MiniMessageError> {
+        MiniMessage::with_config(MiniMessageConfig {
+            unknown_tags: policy,
+            ..Default::default()
+        })
+        .parse(input)
+    }
+
+    #[test]
+    fn test_unknown_tag_as_text() {
+        let comp = parse_with_policy("<unknown>hi</unknown>", UnknownTagPolicy::AsText).unwrap();
+        assert_eq!(comp.to_plain_text(), "<unknown>hi</unknown>");
+    }
+
+    #[test]
+    fn test_unknown_tag_error() {
+        assert!(parse_with_policy("<unknown>hi</unknown>", UnknownTagPolicy::Error).is_err());
+    }
+
+    #[test]
+    fn test_unknown_tag_strip() {
+        let comp = parse_with_policy("a<unknown>hi</unknown>b", UnknownTagPolicy::Strip).unwrap();
+        assert_eq!(comp.to_plain_text(), "ab");
+
+        let nested = parse_with_policy(
+            "<unknown>x<unknown>y</unknown>z</unknown><red>!</red>",
+            UnknownTagPolicy::Strip,
+        )
+        .unwrap();
+        assert_eq!(nested.to_plain_text(), "!");
+    }
+
+    #[test]
+    fn test_self_closing_style_applies_to_rest() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let comp = MiniMessage::new().parse("a<red/>b").unwrap();
+        assert_eq!(
+            comp,
+            Component::Array(vec![
+                Component::text("a"),
+                Component::text("b").color(red.clone())
+            ])
+        );
+
+        // The sticky style ends with the enclosing tag
+        let comp = MiniMessage::new().parse("<bold>x<red/>y</bold>z").unwrap();
+        assert_eq!(
+            comp.get_children()[3],
+            Component::text("z"),
+            "closing <bold> should also drop the self-closing color"
+        );
+        assert_eq!(
+            comp.get_children()[2],
+            Component::text("y")
+                .color(red)
+                .decoration(TextDecoration::Bold, Some(true))
+        );
+    }
+
+    #[test]
+    fn test_self_closing_unknown_tag_has_no_empty_node() {
+        let comp = parse_with_policy("a<unknown/>b", UnknownTagPolicy::AsText).unwrap();
+        assert_eq!(comp.to_plain_text(), "a<unknown/>b");
+
+        let comp = parse_with_policy("a<unknown/>b", UnknownTagPolicy::Strip).unwrap();
+        assert_eq!(
+            comp,
+            Component::Array(vec![Component::text("a"), Component::text("b")])
+        );
+    }
+
+    #[test]
+    fn test_newline_serialization_modes() {
+        let text = Component::text("a\nb").color(Some(Color::Named(NamedColor::Red)));
+        let raw = MiniMessage::new().serialize(&text).unwrap();
+        assert_eq!(raw, "<red>a\nb</red>");
+
+        let tagged = MiniMessage::with_config(MiniMessageConfig {
+            newline_as_tag: true,
+            ..Default::default()
+        })
+        .serialize(&text)
+        .unwrap();
+        assert_eq!(tagged, "<red>a<newline>b</red>");
+
+        // Both forms parse back to the same visible text
+        for input in [raw, tagged] {
+            let parsed = MiniMessage::new().parse(&input).unwrap();
+            assert_eq!(parsed.to_plain_text(), "a\nb");
+        }
+    }
+
+    fn shadow_of(comp: &Component) -> Option<ShadowColor> {
+        match comp {
+            Component::Object(obj) => obj.shadow_color,
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_shadow_tag() {
+        let black = MiniMessage::new()
+            .parse("<shadow:#000000ff>text</shadow>")
+            .unwrap();
+        assert_eq!(black.to_plain_text(), "text");
+        assert_eq!(shadow_of(&black), Some(ShadowColor::Int(-16777216)));
+        assert_eq!(shadow_of(&black).unwrap().to_rgba(), [0, 0, 0, 255]);
+
+        // A color with an optional alpha, defaulting to 25% opacity
+        let red = MiniMessage::new().parse("<shadow:red>a</shadow>").unwrap();
+        assert_eq!(
+            shadow_of(&red),
+            Some(ShadowColor::from_rgba([0xFF, 0x55, 0x55, 64]))
+        );
+        let half = MiniMessage::new()
+            .parse("<shadow:#102030:0.5>a</shadow>b")
+            .unwrap();
+        let Component::Array(parts) = &half else {
+            panic!("expected three parts, got {half:?}");
+        };
+        assert_eq!(
+            shadow_of(&parts[1]),
+            Some(ShadowColor::from_rgba([0x10, 0x20, 0x30, 128]))
+        );
+        assert_eq!(shadow_of(&parts[2]), None);
+
+        let strict = MiniMessage::with_config(MiniMessageConfig {
+            strict: true,
+            ..Default::default()
+        });
+        for invalid in [
+            "<shadow>a",
+            "<shadow:#12345g78>a",
+            "<shadow:red:2>a",
+            "<shadow:#+1234567>a",
+        ] {
+            assert!(
+                strict.parse(invalid).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+        let lenient = MiniMessage::new().parse("<shadow:red:2>a").unwrap();
+        assert_eq!(lenient.to_plain_text(), "<shadow:red:2>a");
+    }
+
+    #[test]
+    fn test_shadow_round_trip() {
+        let shadow = Some(ShadowColor::from_rgba([0x12, 0x34, 0x56, 0x78]));
+        let comp = Component::text("a")
+            .map_object(|mut obj| {
+                obj.shadow_color = shadow;
+                obj
+            })
+            .append(Component::text("b"));
+        let serialized = MiniMessage::new().serialize(&comp).unwrap();
+        assert_eq!(serialized, "<shadow:#12345678>ab</shadow>");
+        assert_round_trip(&comp);
+    }
+
+    #[test]
+    fn test_escaped_text_round_trips() {
+        let text = r"Tom & Jerry <3 §a \o/";
+        let comp = Component::text(text);
+
+        let plain = MiniMessage::new();
+        let serialized = plain.serialize(&comp).unwrap();
+        assert_eq!(serialized, r"Tom & Jerry \<3 §a \\o/");
+        assert_eq!(plain.parse(&serialized).unwrap().to_plain_text(), text);
+
+        let legacy = MiniMessage::with_config(MiniMessageConfig {
+            parse_legacy_colors: true,
+            ..Default::default()
+        });
+        let serialized = legacy.serialize(&comp).unwrap();
+        assert_eq!(serialized, r"Tom \& Jerry \<3 \§a \\o/");
+        assert_eq!(legacy.parse(&serialized).unwrap().to_plain_text(), text);
+
+        // A backslash before anything else is literal, and escapes keep tags from being parsed
+        assert_eq!(
+            plain.parse(r"\<red>a\b").unwrap().to_plain_text(),
+            r"<red>a\b"
+        );
+    }
+
+    #[test]
+    fn test_translatable_round_trip() {
+        let chat = ComponentObject {
+            translate: Some("chat.type.text".into()),
+            with: Some(vec![
+                Component::text("Steve").color(Some(Color::Named(NamedColor::Red))),
+                Component::text("it's: fine"),
+            ]),
+            ..Default::default()
+        };
+        let comp = Component::Object(Box::new(chat)).color(Some(Color::Named(NamedColor::Gold)));
+
+        let serialized = MiniMessage::new().serialize(&comp).unwrap();
+        assert_eq!(
+            serialized,
+            r"<gold><lang:chat.type.text:'<red>Steve</red>':'it\'s: fine'></gold>"
+        );
+
+        let reparsed = MiniMessage::new().parse(&serialized).unwrap();
+        let table = HashMap::from([("chat.type.text".to_string(), "<%s> %s".to_string())]);
+        assert_eq!(
+            styled_text(&reparsed.resolve_translations(&table)),
+            styled_text(&comp.resolve_translations(&table))
+        );
+    }
+
+    #[test]
+    fn test_content_tags_round_trip() {
+        let cases = [
+            (
+                ComponentObject {
+                    translate: Some("missing.key".into()),
+                    fallback: Some("Fallback".into()),
+                    ..Default::default()
+                },
+                "<lang_or:missing.key:Fallback>",
+            ),
+            (
+                ComponentObject {
+                    keybind: Some("key.jump".into()),
+                    ..Default::default()
+                },
+                "<key:key.jump>",
+            ),
+            (
+                ComponentObject {
+                    selector: Some("@a[distance=..5]".into()),
+                    separator: Some(Box::new(Component::text(", "))),
+                    ..Default::default()
+                },
+                "<selector:@a[distance=..5]:', '>",
+            ),
+            (
+                ComponentObject {
+                    score: Some(ScoreContent::new("@s", "kills")),
+                    ..Default::default()
+                },
+                "<score:@s:kills>",
+            ),
+            (
+                ComponentObject {
+                    nbt: Some("Items[0]".into()),
+                    block: Some("1 64 -3".into()),
+                    interpret: Some(true),
+                    ..Default::default()
+                },
+                "<nbt:block:'1 64 -3':Items[0]:interpret>",
+            ),
+        ];
+        for (obj, expected) in cases {
+            let comp = Component::Object(Box::new(obj));
+            let serialized = MiniMessage::new().serialize(&comp).unwrap();
+            assert_eq!(serialized, expected);
+            assert_eq!(MiniMessage::new().parse(&serialized).unwrap(), comp);
+        }
+
+        let sourceless = Component::Object(Box::new(ComponentObject {
+            nbt: Some("Health".into()),
+            ..Default::default()
+        }));
+        assert!(MiniMessage::new().serialize(&sourceless).is_err());
+    }
+
+    #[test]
+    fn test_mismatched_close_tags() {
+        let strict = MiniMessage::with_config(MiniMessageConfig {
+            strict: true,
+            ..Default::default()
+        });
+        let err = strict.parse("<red>hi</blue>").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "MiniMessage error: Closing tag '</blue>' does not match open tag '<red>'"
+        );
+        assert!(strict.parse("<red><bold>hi</red></bold>").is_err());
+        assert!(strict.parse("hi</bold>").is_err());
+        // Aliases and sticky tags still close cleanly
+        assert!(strict.parse("<b>hi</bold> <c:red>x</colour>").is_ok());
+        assert!(strict.parse("<bold>x<red/>y</bold>").is_ok());
+        assert!(strict.parse("<bold>x<red/>y</red>z</bold>").is_ok());
+
+        // Lenient mode closes the nearest matching tag, leaving the ones opened inside it open
+        let red = Some(Color::Named(NamedColor::Red));
+        let bold = |text| Component::text(text).decoration(TextDecoration::Bold, Some(true));
+        let comp = MiniMessage::new().parse("<red>a<bold>b</red>c").unwrap();
+        assert_eq!(comp.get_children()[3], bold("c"));
+
+        // and pops the innermost tag when nothing matches
+        let comp = MiniMessage::new().parse("<red>a<bold>b</blue>c").unwrap();
+        assert_eq!(comp.get_children()[3], Component::text("c").color(red));
+    }
 }
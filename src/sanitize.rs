@@ -0,0 +1,216 @@
+//! Cleaning untrusted components before they are displayed.
+
+use crate::{ClickEvent, Color, Component, HoverEvent};
+
+/// What [`Component::sanitize`] removes from a component. Every option is off by default, so
+/// `SanitizePolicy::default()` leaves components unchanged.
+///
+/// ```
+/// use kyori_component_json::*;
+///
+/// let policy = SanitizePolicy {
+///     strip_hover_events: true,
+///     safe_click_events_only: true,
+///     ..Default::default()
+/// };
+/// let message = Component::text("Free stuff")
+///     .click_run("/op @s")
+///     .hover_text("Trust me");
+/// assert_eq!(message.sanitize(&policy), Component::text("Free stuff"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SanitizePolicy {
+    /// Removes every click event
+    pub strip_click_events: bool,
+    /// Keeps only click events that need the player to act again before anything happens,
+    /// `open_url` (the game asks for confirmation) and `suggest_command` (the command is only
+    /// typed into the chat box). Commands, files, page changes and clipboard copies are
+    /// removed.
+    pub safe_click_events_only: bool,
+    /// Removes every hover event, hover text included
+    pub strip_hover_events: bool,
+    /// Removes the text inserted into the chat box on shift-click
+    pub strip_insertion: bool,
+    /// Removes the obfuscated decoration, so scrambled text is shown as what it really says
+    pub strip_obfuscation: bool,
+    /// Removes hex text colors, leaving the text in the color it inherits. Named colors are
+    /// kept.
+    pub strip_hex_colors: bool,
+    /// Removes custom fonts, which can make text invisible or look like something else
+    pub strip_fonts: bool,
+    /// Removes every nested component deeper than this, counting the root as depth 1 like
+    /// [`Limits::max_depth`](crate::limits::Limits::max_depth). Hover text that would be too
+    /// deep is removed with its hover event. `Some(0)` removes everything.
+    pub max_depth: Option<usize>,
+}
+
+impl SanitizePolicy {
+    /// A policy for showing components from untrusted players: no hover events, insertion,
+    /// obfuscation or custom fonts, only safe click events, and at most 8 levels of nesting.
+    /// Colors are kept.
+    pub fn untrusted() -> Self {
+        SanitizePolicy {
+            strip_click_events: false,
+            safe_click_events_only: true,
+            strip_hover_events: true,
+            strip_insertion: true,
+            strip_obfuscation: true,
+            strip_hex_colors: false,
+            strip_fonts: true,
+            max_depth: Some(8),
+        }
+    }
+}
+
+impl Component {
+    /// Cleans this component according to `policy`, removing what it allows players to abuse.
+    /// Applies to the whole tree, including hover text, translation arguments and separators.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let hostile = Component::text("Click")
+    ///     .decoration(TextDecoration::Obfuscated, Some(true))
+    ///     .click_event(Some(ClickEvent::CopyToClipboard { value: "scam".into() }))
+    ///     .append(Component::text(" here").click_event(Some(ClickEvent::SuggestCommand {
+    ///         command: "/help".into(),
+    ///     })));
+    /// assert_eq!(
+    ///     hostile.sanitize(&SanitizePolicy::untrusted()),
+    ///     Component::text("Click").append(Component::text(" here").click_event(Some(
+    ///         ClickEvent::SuggestCommand { command: "/help".into() }
+    ///     )))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn sanitize(mut self, policy: &SanitizePolicy) -> Self {
+        match policy.max_depth {
+            Some(0) => return Component::String(String::new()),
+            Some(max_depth) => self.prune_depth(max_depth),
+            None => {}
+        }
+        self.visit_objects_mut(&mut |obj| {
+            if policy.strip_click_events
+                || policy.safe_click_events_only
+                    && !matches!(
+                        obj.click_event,
+                        Some(ClickEvent::OpenUrl { .. } | ClickEvent::SuggestCommand { .. })
+                    )
+            {
+                obj.click_event = None;
+            }
+            if policy.strip_hover_events {
+                obj.hover_event = None;
+            }
+            if policy.strip_insertion {
+                obj.insertion = None;
+            }
+            if policy.strip_obfuscation {
+                obj.obfuscated = None;
+            }
+            if policy.strip_hex_colors && matches!(obj.color, Some(Color::Hex(_))) {
+                obj.color = None;
+            }
+            if policy.strip_fonts {
+                obj.font = None;
+            }
+        });
+        self
+    }
+
+    /// Removes the components nested below `levels` levels, this one being the first
+    fn prune_depth(&mut self, levels: usize) {
+        match self {
+            Component::String(_) => {}
+            Component::Array(children) if levels <= 1 => children.clear(),
+            Component::Array(children) => {
+                for child in children {
+                    child.prune_depth(levels - 1);
+                }
+            }
+            Component::Object(obj) if levels <= 1 => {
+                obj.with = None;
+                obj.separator = None;
+                obj.extra = None;
+                match &mut obj.hover_event {
+                    Some(HoverEvent::ShowText { .. }) => obj.hover_event = None,
+                    Some(HoverEvent::ShowEntity { name, .. }) => *name = None,
+                    _ => {}
+                }
+            }
+            Component::Object(obj) => {
+                for child in obj.nested_mut() {
+                    child.prune_depth(levels - 1);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_sanitize_hostile_component() {
+        let nested = (0..10).fold(Component::text("deep"), |inner, _| {
+            Component::text("").append(inner)
+        });
+        let hostile = Component::text("Win ")
+            .color(Some(Color::Hex("#FF00FF".into())))
+            .font(Some("custom:invisible".into()))
+            .insertion(Some("/op @s".into()))
+            .click_run("/op @s")
+            .hover_text(Component::text("Totally safe").click_run("/kill @a"))
+            .append(
+                Component::text("prizes")
+                    .color(Some(Color::Named(NamedColor::Gold)))
+                    .decoration(TextDecoration::Obfuscated, Some(true))
+                    .click_event(Some(ClickEvent::OpenUrl {
+                        url: "https://example.com".into(),
+                    })),
+            )
+            .append(nested);
+
+        let strict = SanitizePolicy {
+            strip_click_events: true,
+            strip_hex_colors: true,
+            max_depth: Some(3),
+            ..SanitizePolicy::untrusted()
+        };
+        let cleaned = hostile.clone().sanitize(&strict);
+        assert_eq!(
+            cleaned,
+            Component::text("Win ")
+                .append(Component::text("prizes").color(Some(Color::Named(NamedColor::Gold))))
+                .append(Component::text("").append(Component::text("")))
+        );
+        assert_eq!(cleaned.depth(), 3);
+
+        // The untrusted preset keeps colors and safe click events, and deeper nesting
+        let cleaned = hostile.clone().sanitize(&SanitizePolicy::untrusted());
+        let children = cleaned.get_children();
+        assert_eq!(
+            cleaned.as_object().unwrap().color,
+            Some(Color::Hex("#FF00FF".into()))
+        );
+        assert!(cleaned.as_object().unwrap().click_event.is_none());
+        assert!(matches!(
+            children[0].as_object().unwrap().click_event,
+            Some(ClickEvent::OpenUrl { .. })
+        ));
+        assert_eq!(cleaned.depth(), 8);
+
+        assert_eq!(
+            hostile.clone().sanitize(&SanitizePolicy::default()),
+            hostile
+        );
+        assert_eq!(
+            hostile.sanitize(&SanitizePolicy {
+                max_depth: Some(0),
+                ..Default::default()
+            }),
+            Component::String(String::new())
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Embedding components in SNBT, the text form of NBT used by commands like `/data merge`.
+//!
+//! Signs, books and custom names store their text as NBT strings holding the component's JSON.
+//! To write one in a command, the JSON has to be quoted as an SNBT string, following the same
+//! rules as the game:
+//!
+//! - Backslashes are escaped as `\\`.
+//! - The string is wrapped in single quotes if it contains a `"` before any `'`, otherwise in
+//!   double quotes. Since component JSON always contains `"`, it's usually single-quoted.
+//! - Occurrences of the chosen quote character inside the string are escaped with `\`.
+//!
+//! Text is stored this way up to Minecraft 1.21.4; since 1.21.5 it's stored as SNBT compounds,
+//! which this module doesn't produce. The JSON is therefore written the way those versions
+//! read it, with click and hover events under the older `clickEvent` and `hoverEvent` keys
+//! (see [`Component::to_json_for`]).
+
+use crate::Component;
+use crate::version::to_legacy_events;
+
+/// Quotes `s` as an SNBT string literal, picking the quote character like Minecraft does.
+///
+/// ```
+/// use kyori_component_json::snbt::quote;
+///
+/// assert_eq!(quote("plain"), r#""plain""#);
+/// assert_eq!(quote(r#"{"text":"it's"}"#), r#"'{"text":"it\'s"}'"#);
+/// ```
+pub fn quote(s: &str) -> String {
+    let mut quote = None;
+    let mut body = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => body.push_str("\\\\"),
+            '"' | '\'' => {
+                let quote = *quote.get_or_insert(if c == '"' { '\'' } else { '"' });
+                if c == quote {
+                    body.push('\\');
+                }
+                body.push(c);
+            }
+            _ => body.push(c),
+        }
+    }
+    let quote = quote.unwrap_or('"');
+    format!("{quote}{body}{quote}")
+}
+
+/// Builds the SNBT of a sign's text, for e.g. `/data merge block <pos> <snbt>`.
+///
+/// The lines go on the front of the sign, in the `front_text` compound used since
+/// Minecraft 1.20.
+///
+/// # Errors
+///
+/// Returns an error if a line fails to serialize to JSON.
+pub fn sign_block_entity(front: &[Component; 4]) -> Result<String, serde_json::Error> {
+    let messages = front
+        .iter()
+        .map(Component::to_snbt)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(format!(
+        "{{front_text:{{messages:[{}]}}}}",
+        messages.join(",")
+    ))
+}
+
+/// Builds the SNBT tag of a written book with the given pages, for e.g.
+/// `/give @p written_book<snbt>` on versions before item components (1.20.5).
+///
+/// # Errors
+///
+/// Returns an error if a page fails to serialize to JSON.
+pub fn written_book(
+    title: &str,
+    author: &str,
+    pages: &[Component],
+) -> Result<String, serde_json::Error> {
+    let pages = pages
+        .iter()
+        .map(Component::to_snbt)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(format!(
+        "{{title:{},author:{},pages:[{}]}}",
+        quote(title),
+        quote(author),
+        pages.join(",")
+    ))
+}
+
+impl Component {
+    /// Serializes this component to JSON and quotes it as an SNBT string, the form it takes
+    /// inside NBT such as sign text or book pages before 1.21.5. See the
+    /// [`snbt`](crate::snbt) module for the quoting rules.
+    ///
+    /// Click and hover events are written with the keys those versions read, `clickEvent`
+    /// and `hoverEvent`. Nothing else is downgraded.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// assert_eq!(Component::text("Hi").to_snbt().unwrap(), r#"'{"text":"Hi"}'"#);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component fails to serialize to JSON.
+    pub fn to_snbt(&self) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        to_legacy_events(&mut value);
+        serde_json::to_string(&value).map(|json| quote(&json))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClickEvent;
+
+    #[test]
+    fn test_quote_escaping() {
+        assert_eq!(quote(""), r#""""#);
+        assert_eq!(quote("it's"), r#""it's""#);
+        assert_eq!(quote(r#"say "hi""#), r#"'say "hi"'"#);
+        assert_eq!(quote(r#"a\b"#), r#""a\\b""#);
+        // The first quote character decides, later ones of the same kind are escaped
+        assert_eq!(quote(r#"'x' "y""#), r#""'x' \"y\"""#);
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        let lines = [
+            Component::text("Welcome"),
+            Component::String("to the".into()),
+            Component::text("Shop's"),
+            Component::default(),
+        ];
+        assert_eq!(
+            sign_block_entity(&lines).unwrap(),
+            r#"{front_text:{messages:['{"text":"Welcome"}','"to the"','{"text":"Shop\'s"}','""']}}"#
+        );
+    }
+
+    #[test]
+    fn test_events_use_legacy_keys() {
+        let page = Component::text("Next").click_event(Some(ClickEvent::ChangePage { page: 2 }));
+        let snbt = page.to_snbt().unwrap();
+        let json: serde_json::Value = serde_json::from_str(&snbt[1..snbt.len() - 1]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "text": "Next",
+                "clickEvent": {"action": "change_page", "value": "2"}
+            })
+        );
+
+        let book = written_book("Guide", "Steve", &[page]).unwrap();
+        assert!(book.contains(r#""clickEvent":{"action":"change_page","value":"2"}"#));
+        assert!(!book.contains("click_event"));
+    }
+
+    #[test]
+    fn test_written_book_payload() {
+        let pages = [Component::text("Page \\ one")];
+        assert_eq!(
+            written_book("Guide", "Steve", &pages).unwrap(),
+            r#"{title:"Guide",author:"Steve",pages:['{"text":"Page \\\\ one"}']}"#
+        );
+    }
+}
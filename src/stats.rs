@@ -0,0 +1,100 @@
+//! Summaries of the styling used in a component tree.
+
+use crate::{Color, Component, TextDecoration};
+use std::collections::{HashMap, HashSet};
+
+/// Statistics about the styling of a component tree, returned by [`Component::style_stats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StyleStats {
+    /// Number of distinct text colors set in the tree. Colors are compared exactly, so a
+    /// named color and its hex value count as two.
+    pub distinct_colors: usize,
+    /// Whether any text color is a hex color, which clients before 1.16 can't display
+    pub has_hex_colors: bool,
+    /// Number of levels in the tree: 1 for a single component, plus one for each level of
+    /// children, translation arguments, separators, hover text or array elements
+    pub max_depth: usize,
+    /// For each decoration, the number of components that explicitly enable it
+    pub decorations: HashMap<TextDecoration, usize>,
+}
+
+impl Component {
+    /// Collects statistics about the styling used anywhere in the tree, including hover text,
+    /// translation arguments and separators.
+    ///
+    /// Useful to check a message against what a server or client supports, e.g. to warn
+    /// about hex colors that won't render before 1.16.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("a")
+    ///     .color(Some(Color::Named(NamedColor::Red)))
+    ///     .append(Component::text("b").color(Some(Color::Hex("#123456".into()))));
+    /// let stats = message.style_stats();
+    /// assert_eq!(stats.distinct_colors, 2);
+    /// assert!(stats.has_hex_colors);
+    /// assert_eq!(stats.max_depth, 2);
+    /// ```
+    pub fn style_stats(&self) -> StyleStats {
+        let mut colors: HashSet<&Color> = HashSet::new();
+        let mut decorations = HashMap::new();
+        for node in self.nodes() {
+            let Component::Object(obj) = node else {
+                continue;
+            };
+            colors.extend(&obj.color);
+            for (decoration, _) in node.decoration_set().iter().filter(|(_, on)| *on) {
+                *decorations.entry(decoration).or_insert(0) += 1;
+            }
+        }
+        StyleStats {
+            distinct_colors: colors.len(),
+            has_hex_colors: colors.iter().any(|color| matches!(color, Color::Hex(_))),
+            max_depth: self.depth(),
+            decorations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_style_stats_mixed_colors() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let message = Component::text("a")
+            .color(red.clone())
+            .decoration(TextDecoration::Bold, Some(true))
+            .append(
+                Component::text("b")
+                    .color(Some(Color::Hex("#FF5555".into())))
+                    .hover_text(
+                        Component::text("tip")
+                            .color(red)
+                            .decoration(TextDecoration::Bold, Some(true))
+                            .decoration(TextDecoration::Italic, Some(true)),
+                    ),
+            )
+            .append(Component::text("c").decoration(TextDecoration::Italic, Some(false)));
+
+        let stats = message.style_stats();
+        assert_eq!(stats.distinct_colors, 2);
+        assert!(stats.has_hex_colors);
+        assert_eq!(stats.max_depth, 3);
+        assert_eq!(
+            stats.decorations,
+            HashMap::from([(TextDecoration::Bold, 2), (TextDecoration::Italic, 1)])
+        );
+
+        let plain = Component::from("plain").style_stats();
+        assert_eq!(
+            plain,
+            StyleStats {
+                max_depth: 1,
+                ..Default::default()
+            }
+        );
+    }
+}
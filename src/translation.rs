@@ -0,0 +1,302 @@
+//! Client-side resolution of translatable components.
+//!
+//! Minecraft formats translations with a small subset of Java's `String.format`: `%s` takes
+//! the next argument, `%1$s` takes an argument by its 1-based index and `%%` is a literal `%`.
+
+use crate::{Component, ComponentObject, ContentType};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// A piece of a decomposed format string
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Literal(Cow<'a, str>),
+    Arg(usize),
+}
+
+/// Splits `format` into literal text and argument references.
+///
+/// Returns `None` for anything the game rejects: unknown conversions, a dangling `%` or an
+/// argument index outside `0..arg_count`.
+fn decompose(format: &str, arg_count: usize) -> Option<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut next_arg = 0;
+    let mut rest = format;
+
+    while let Some(percent) = rest.find('%') {
+        literal.push_str(&rest[..percent]);
+        rest = &rest[percent + 1..];
+
+        if let Some(after) = rest.strip_prefix('%') {
+            literal.push('%');
+            rest = after;
+            continue;
+        }
+
+        let index = if let Some(after) = rest.strip_prefix('s') {
+            rest = after;
+            next_arg += 1;
+            next_arg - 1
+        } else {
+            let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+            let after = rest[digits..].strip_prefix("$s")?;
+            let index: usize = rest[..digits].parse().ok()?;
+            rest = after;
+            index.checked_sub(1)?
+        };
+        if index >= arg_count {
+            return None;
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(Cow::Owned(std::mem::take(&mut literal))));
+        }
+        segments.push(Segment::Arg(index));
+    }
+
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(Cow::Owned(literal)));
+    }
+    Some(segments)
+}
+
+impl Component {
+    /// Replaces every translatable node with text, the way a client would render it.
+    ///
+    /// The translation key is looked up in `table`; when it's missing, the node's `fallback` is
+    /// used, then the key itself. `with` arguments are substituted for `%s` and `%1$s`, and
+    /// `%%` becomes a literal `%`. Arguments keep their own styling, so they are placed as
+    /// children of the resulting text node, ahead of its original `extra`. A format string the
+    /// game would reject (e.g. referring to a missing argument) is shown literally.
+    ///
+    /// Translations nested in arguments, hover text and separators are resolved too.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let table = HashMap::from([("greet".to_string(), "Hello, %s!".to_string())]);
+    /// let mut greeting = ComponentObject::default();
+    /// greeting.translate = Some("greet".into());
+    /// greeting.with = Some(vec![Component::text("Steve")]);
+    ///
+    /// let resolved = Component::Object(Box::new(greeting)).resolve_translations(&table);
+    /// assert_eq!(resolved.to_plain_text(), "Hello, Steve!");
+    /// ```
+    #[must_use]
+    pub fn resolve_translations(self, table: &HashMap<String, String>) -> Self {
+        match self {
+            Component::String(_) => self,
+            Component::Array(children) => Component::Array(
+                children
+                    .into_iter()
+                    .map(|child| child.resolve_translations(table))
+                    .collect(),
+            ),
+            Component::Object(mut obj) => {
+                for child in obj.nested_mut() {
+                    let taken = std::mem::replace(child, Component::String(String::new()));
+                    *child = taken.resolve_translations(table);
+                }
+                if obj.translate.is_some() {
+                    resolve_object(&mut obj, table);
+                }
+                Component::Object(obj)
+            }
+        }
+    }
+
+    /// Sets a `fallback` on every translatable node that doesn't have one, from `fallback`
+    /// called with the node's key, so players see readable text instead of the raw key when
+    /// their client lacks the translation. Keys for which `fallback` returns `None` are left
+    /// without one.
+    ///
+    /// Translations nested in arguments, hover text and separators get fallbacks too.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    /// use std::collections::HashMap;
+    ///
+    /// let english = HashMap::from([("myplugin.bye", "Goodbye!")]);
+    /// let message = Component::translatable("myplugin.bye")
+    ///     .ensure_fallbacks(|key| english.get(key).map(|text| text.to_string()));
+    /// assert_eq!(
+    ///     message,
+    ///     Component::translatable_with_fallback("myplugin.bye", "Goodbye!")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn ensure_fallbacks(mut self, fallback: impl Fn(&str) -> Option<String>) -> Self {
+        self.visit_objects_mut(&mut |obj| {
+            if obj.fallback.is_none()
+                && let Some(key) = &obj.translate
+            {
+                obj.fallback = fallback(key);
+            }
+        });
+        self
+    }
+}
+
+/// Turns a translatable object into a text object, keeping its style and children
+fn resolve_object(obj: &mut ComponentObject, table: &HashMap<String, String>) {
+    let key = obj.translate.take().unwrap_or_default();
+    let fallback = obj.fallback.take();
+    let args = obj.with.take().unwrap_or_default();
+    let format = table
+        .get(&key)
+        .map(String::as_str)
+        .or(fallback.as_deref())
+        .unwrap_or(&key);
+
+    let segments = decompose(format, args.len())
+        .unwrap_or_else(|| vec![Segment::Literal(Cow::Borrowed(format))]);
+    let mut segments = segments.into_iter().peekable();
+
+    let text = match segments.next_if(|segment| matches!(segment, Segment::Literal(_))) {
+        Some(Segment::Literal(text)) => text.into_owned(),
+        _ => String::new(),
+    };
+    let mut children: Vec<Component> = segments
+        .map(|segment| match segment {
+            Segment::Literal(text) => Component::String(text.into_owned()),
+            Segment::Arg(index) => args[index].clone(),
+        })
+        .collect();
+
+    if let Some(content_type) = &mut obj.content_type
+        && *content_type == ContentType::Translatable
+    {
+        *content_type = ContentType::Text;
+    }
+    obj.text = Some(text);
+    if !children.is_empty() {
+        children.extend(obj.extra.take().unwrap_or_default());
+        obj.extra = Some(children);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HoverEvent;
+
+    fn translatable(key: &str, args: Vec<Component>) -> ComponentObject {
+        ComponentObject {
+            translate: Some(key.to_string()),
+            with: (!args.is_empty()).then_some(args),
+            ..Default::default()
+        }
+    }
+
+    fn table() -> HashMap<String, String> {
+        HashMap::from([
+            ("chat".to_string(), "<%s> %s".to_string()),
+            ("swap".to_string(), "%2$s then %1$s".to_string()),
+            ("percent".to_string(), "100%% of %s".to_string()),
+            ("broken".to_string(), "%s and %s".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_positional_args() {
+        let args = vec![Component::text("a"), Component::text("b")];
+        let chat = Component::Object(Box::new(translatable("chat", args.clone())));
+        assert_eq!(chat.resolve_translations(&table()).to_plain_text(), "<a> b");
+
+        let swapped = Component::Object(Box::new(translatable("swap", args)));
+        let resolved = swapped.resolve_translations(&table());
+        assert_eq!(resolved.to_plain_text(), "b then a");
+        assert_eq!(
+            resolved,
+            Component::text("").set_children(vec![
+                Component::text("b"),
+                Component::from(" then "),
+                Component::text("a"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_ensure_fallbacks() {
+        let message = Component::translatable_with_fallback("custom.kept", "Kept %s")
+            .with_arg(Component::translatable("custom.item"))
+            .hover_text(Component::translatable("custom.unknown"));
+        let filled = message.ensure_fallbacks(|key| {
+            key.strip_prefix("custom.")
+                .filter(|name| *name != "unknown")
+                .map(|name| format!("[{name}]"))
+        });
+
+        let obj = filled.as_object().unwrap();
+        assert_eq!(obj.fallback.as_deref(), Some("Kept %s"));
+        assert_eq!(
+            obj.with.as_ref().unwrap()[0],
+            Component::translatable_with_fallback("custom.item", "[item]")
+        );
+        let Some(HoverEvent::ShowText { value }) = &obj.hover_event else {
+            panic!("expected hover text");
+        };
+        assert_eq!(value, &Component::translatable("custom.unknown"));
+
+        // Without the keys in the table, the fallbacks are what gets shown
+        assert_eq!(
+            filled.resolve_translations(&table()).to_plain_text(),
+            "Kept [item]"
+        );
+    }
+
+    #[test]
+    fn test_missing_key_uses_fallback() {
+        let mut obj = translatable("unknown.key", vec![]);
+        obj.fallback = Some("Fallback".to_string());
+        let resolved = Component::Object(Box::new(obj)).resolve_translations(&table());
+        assert_eq!(resolved, Component::text("Fallback"));
+
+        let bare = Component::Object(Box::new(translatable("unknown.key", vec![])));
+        assert_eq!(
+            bare.resolve_translations(&table()),
+            Component::text("unknown.key")
+        );
+    }
+
+    #[test]
+    fn test_percent_escape_and_invalid_format() {
+        let percent = Component::Object(Box::new(translatable(
+            "percent",
+            vec![Component::text("it")],
+        )));
+        assert_eq!(
+            percent.resolve_translations(&table()).to_plain_text(),
+            "100% of it"
+        );
+
+        // Only one argument for two `%s`, so the format is shown as-is
+        let broken =
+            Component::Object(Box::new(translatable("broken", vec![Component::text("x")])));
+        assert_eq!(
+            broken.resolve_translations(&table()).to_plain_text(),
+            "%s and %s"
+        );
+    }
+
+    #[test]
+    fn test_nested_translations_and_style() {
+        let inner = translatable("percent", vec![Component::text("all")]);
+        let mut outer = translatable("chat", vec![Component::text("Server")]);
+        outer.with = Some(vec![
+            Component::text("Server"),
+            Component::Object(Box::new(inner)),
+        ]);
+        outer.color = Some(crate::Color::Named(crate::NamedColor::Gray));
+
+        let resolved = Component::Object(Box::new(outer)).resolve_translations(&table());
+        assert_eq!(resolved.to_plain_text(), "<Server> 100% of all");
+        assert!(matches!(
+            &resolved,
+            Component::Object(obj) if obj.color.is_some() && obj.translate.is_none()
+        ));
+    }
+}
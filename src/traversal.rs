@@ -0,0 +1,1375 @@
+//! Recursive traversal of component trees.
+//!
+//! Besides `extra`, a component can nest other components inside its translation arguments
+//! (`with`), its `separator` and its hover event. The helpers in this module visit all of them.
+//!
+//! Every transform of a whole tree (`transform_objects`, `map_colors`, `flatten_styles`,
+//! `apply_fallback_style`, `resolve_translations`, ...) must descend into all of these, which
+//! [`ComponentObject::nested_mut`] and [`Component::with_children_mapped`] make easy. Only
+//! helpers about the visible text, like `font_all` or `styled_runs`, stick to `extra`.
+
+use crate::{
+    ClickEvent, Color, Component, ComponentObject, ContentType, EMPTY_STYLE, HoverEvent, Style,
+};
+use std::ops::ControlFlow;
+
+/// Pre-order iterator over a component and every component nested inside it.
+///
+/// Created by [`Component::nodes`].
+#[derive(Debug, Clone)]
+pub struct Nodes<'a> {
+    stack: Vec<&'a Component>,
+}
+
+impl<'a> Iterator for Nodes<'a> {
+    type Item = &'a Component;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let start = self.stack.len();
+        match node {
+            Component::String(_) => {}
+            Component::Array(children) => self.stack.extend(children),
+            Component::Object(obj) => self.stack.extend(obj.nested()),
+        }
+        // Children are pushed in order, so reverse them to pop the first one next
+        self.stack[start..].reverse();
+        Some(node)
+    }
+}
+
+/// A run of text with its fully resolved style, as returned by [`Component::to_spans`].
+///
+/// The style has inheritance already applied, so it holds everything that affects the run:
+/// formatting, but also the insertion, click and hover events it responds to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyledSpan {
+    /// The text of the run
+    pub text: String,
+    /// The resolved style of the run
+    pub style: Style,
+}
+
+impl ComponentObject {
+    /// Iterates over the components directly nested in this object, in the order:
+    /// `with` arguments, `separator`, hover text (or entity name), then `extra` children.
+    pub(crate) fn nested(&self) -> impl Iterator<Item = &Component> {
+        let hover = match &self.hover_event {
+            Some(HoverEvent::ShowText { value }) => Some(value),
+            Some(HoverEvent::ShowEntity {
+                name: Some(name), ..
+            }) => Some(name),
+            _ => None,
+        };
+        self.with
+            .iter()
+            .flatten()
+            .chain(self.separator.as_deref())
+            .chain(hover)
+            .chain(self.extra.iter().flatten())
+    }
+}
+
+impl ComponentObject {
+    /// Mutable counterpart of [`Self::nested`], visiting components in the same order.
+    pub(crate) fn nested_mut(&mut self) -> impl Iterator<Item = &mut Component> {
+        let hover = match &mut self.hover_event {
+            Some(HoverEvent::ShowText { value }) => Some(value),
+            Some(HoverEvent::ShowEntity {
+                name: Some(name), ..
+            }) => Some(name),
+            _ => None,
+        };
+        self.with
+            .iter_mut()
+            .flatten()
+            .chain(self.separator.as_deref_mut())
+            .chain(hover)
+            .chain(self.extra.iter_mut().flatten())
+    }
+
+    /// The component shown by the hover event: its text, or the entity's name
+    fn hover_component_mut(&mut self) -> Option<&mut Component> {
+        match &mut self.hover_event {
+            Some(HoverEvent::ShowText { value }) => Some(value),
+            Some(HoverEvent::ShowEntity {
+                name: Some(name), ..
+            }) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+impl Component {
+    /// Calls `f` on every object node in pre-order, descending into everything
+    /// [`ComponentObject::nested`] visits.
+    pub(crate) fn visit_objects_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut ComponentObject),
+    {
+        match self {
+            Component::String(_) => {}
+            Component::Array(children) => {
+                for child in children {
+                    child.visit_objects_mut(f);
+                }
+            }
+            Component::Object(obj) => {
+                f(obj);
+                for child in obj.nested_mut() {
+                    child.visit_objects_mut(f);
+                }
+            }
+        }
+    }
+
+    /// Replaces every component directly nested in this one with `f` applied to it, in the
+    /// order of [`ComponentObject::nested`]: translation arguments, the separator, hover text
+    /// (or entity name), then `extra` children. Array elements are mapped too. This doesn't
+    /// recurse on its own; `f` can call it again to walk the whole tree without forgetting
+    /// hover text.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// fn shout(component: Component) -> Component {
+    ///     match component.with_children_mapped(shout) {
+    ///         Component::String(text) => Component::String(text.to_uppercase()),
+    ///         Component::Object(mut obj) => {
+    ///             obj.text = obj.text.map(|text| text.to_uppercase());
+    ///             Component::Object(obj)
+    ///         }
+    ///         array => array,
+    ///     }
+    /// }
+    ///
+    /// let loud = shout(Component::text("hi").hover_text("psst"));
+    /// assert_eq!(loud, Component::text("HI").hover_text("PSST"));
+    /// ```
+    #[must_use]
+    pub fn with_children_mapped<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(Component) -> Component,
+    {
+        match self {
+            Component::String(_) => self,
+            Component::Array(children) => Component::Array(children.into_iter().map(f).collect()),
+            Component::Object(mut obj) => {
+                for child in obj.nested_mut() {
+                    let taken = std::mem::replace(child, Component::String(String::new()));
+                    *child = f(taken);
+                }
+                Component::Object(obj)
+            }
+        }
+    }
+
+    /// Calls `f` on every object node of the visible text in pre-order: `extra` children and
+    /// array elements, but not hover text, translation arguments or separators.
+    fn visit_visible_objects_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut ComponentObject),
+    {
+        match self {
+            Component::String(_) => {}
+            Component::Array(children) => {
+                for child in children {
+                    child.visit_visible_objects_mut(f);
+                }
+            }
+            Component::Object(obj) => {
+                f(obj);
+                for child in obj.extra.iter_mut().flatten() {
+                    child.visit_visible_objects_mut(f);
+                }
+            }
+        }
+    }
+
+    /// Turns every array in the tree into an object: the first element becomes the parent and
+    /// the remaining elements are appended to its `extra`.
+    ///
+    /// This is how Minecraft reads arrays, so the rendering is unchanged, but the inheritance
+    /// becomes explicit. Call it before handing a deserialized component to code that treats
+    /// array elements as independent siblings, or before restyling the root, which would
+    /// otherwise only affect the first element. An empty array becomes an empty text object.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let array: Component = serde_json::from_str(r#"["parent", {"text":"child"}]"#).unwrap();
+    /// assert_eq!(
+    ///     array.resolve_array_parent(),
+    ///     Component::text("parent").append(Component::text("child"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn resolve_array_parent(self) -> Self {
+        match self.with_children_mapped(Component::resolve_array_parent) {
+            array @ Component::Array(_) => Component::Object(Box::new(array.into_object())),
+            component => component,
+        }
+    }
+
+    /// Copies every inherited style property onto each node, so every node carries its full
+    /// effective style and no longer depends on its ancestors.
+    ///
+    /// `extra` children, translation arguments and separators inherit from their parent, and
+    /// array elements from the first element. Hover text is displayed on its own, so it's
+    /// flattened separately without inheriting anything. Plain strings that inherit some style
+    /// become text objects. The rendered result is the
+    /// same; the JSON gets larger, but any node can be moved or inspected on its own.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let flat = Component::text("a")
+    ///     .font(Some("minecraft:uniform".into()))
+    ///     .append(Component::text("b"))
+    ///     .flatten_styles();
+    /// assert_eq!(
+    ///     flat.get_children()[0],
+    ///     Component::text("b").font(Some("minecraft:uniform".into()))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn flatten_styles(self) -> Self {
+        self.flatten_styles_from(&EMPTY_STYLE)
+    }
+
+    fn flatten_styles_from(self, parent: &Style) -> Self {
+        match self {
+            Component::String(_) if *parent == EMPTY_STYLE => self,
+            Component::String(_) => self.apply_fallback_style(parent),
+            Component::Array(children) => {
+                let mut children = children.into_iter();
+                let Some(first) = children.next() else {
+                    return Component::Array(Vec::new());
+                };
+                let first = first.flatten_styles_from(parent);
+                // The first element is now fully styled, so its style is what the rest inherit
+                let style = match &first {
+                    Component::Object(obj) => obj.style(),
+                    _ => parent.clone(),
+                };
+                Component::Array(
+                    std::iter::once(first)
+                        .chain(children.map(|child| child.flatten_styles_from(&style)))
+                        .collect(),
+                )
+            }
+            Component::Object(mut obj) => {
+                // Hover text first, so a hover event inherited below isn't flattened again
+                if let Some(hover) = obj.hover_component_mut() {
+                    let taken = std::mem::replace(hover, Component::String(String::new()));
+                    *hover = taken.flatten_styles();
+                }
+                obj.merge_style(parent);
+                let style = obj.style();
+                let inline = obj
+                    .with
+                    .iter_mut()
+                    .flatten()
+                    .chain(obj.separator.as_deref_mut())
+                    .chain(obj.extra.iter_mut().flatten());
+                for child in inline {
+                    let taken = std::mem::replace(child, Component::String(String::new()));
+                    *child = taken.flatten_styles_from(&style);
+                }
+                Component::Object(obj)
+            }
+        }
+    }
+
+    /// Moves style properties that children all share up to their parent, and drops the ones
+    /// they would inherit anyway. The inverse of [`Self::flatten_styles`]: the rendered result
+    /// is the same, with smaller JSON and MiniMessage output.
+    ///
+    /// A property is only hoisted when it's safe to:
+    ///
+    /// - every one of at least two `extra` children is an object setting it to the same value
+    ///   (a plain string or array child sets nothing, so it blocks hoisting), and
+    /// - the parent shows no text of its own, being an empty `text` component, and
+    /// - the parent isn't the first element of an array with other elements, which would
+    ///   inherit the property from it.
+    ///
+    /// Independently, a child property equal to the one its parent sets is removed, and so is
+    /// an array element property equal to the one the first element sets. Hover text is
+    /// handled on its own, as it doesn't inherit anything.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let red = Some(Color::Named(NamedColor::Red));
+    /// let list = Component::text("").set_children(vec![
+    ///     Component::text("a").color(red.clone()),
+    ///     Component::text("b").color(red.clone()),
+    /// ]);
+    /// assert_eq!(
+    ///     list.hoist_common_styles(),
+    ///     Component::text("")
+    ///         .color(red)
+    ///         .append(Component::text("a"))
+    ///         .append(Component::text("b"))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn hoist_common_styles(mut self) -> Self {
+        self.hoist_common_styles_in_place(true);
+        self
+    }
+
+    /// Hoists styles throughout the tree, onto this node itself only if `hoist_here`
+    fn hoist_common_styles_in_place(&mut self, hoist_here: bool) {
+        match self {
+            Component::String(_) => {}
+            Component::Array(children) => {
+                // Later elements inherit the first one's style, so nothing can be hoisted
+                // onto it without reaching them too
+                let has_siblings = children.len() > 1;
+                for (i, child) in children.iter_mut().enumerate() {
+                    child.hoist_common_styles_in_place(i > 0 || !has_siblings);
+                }
+                // The rest of an array inherits the first element's style
+                if let Some((Component::Object(first), rest)) = children.split_first_mut() {
+                    remove_inherited(first, rest);
+                }
+            }
+            Component::Object(obj) => {
+                for child in obj.nested_mut() {
+                    child.hoist_common_styles_in_place(true);
+                }
+                let shows_text = obj.text.as_deref() != Some("")
+                    || obj.inferred_content_type() != Some(ContentType::Text);
+                let Some(children) = obj.extra.as_mut() else {
+                    return;
+                };
+                if hoist_here && !shows_text && children.len() > 1 {
+                    macro_rules! hoist {
+                        ($($field:ident),*) => {$(
+                            let first = children[0].as_object().and_then(|c| c.$field.clone());
+                            if first.is_some()
+                                && children
+                                    .iter()
+                                    .all(|c| c.as_object().is_some_and(|c| c.$field == first))
+                            {
+                                obj.$field = first;
+                            }
+                        )*};
+                    }
+                    hoist!(
+                        color,
+                        font,
+                        bold,
+                        italic,
+                        underlined,
+                        strikethrough,
+                        obfuscated,
+                        shadow_color,
+                        insertion,
+                        click_event,
+                        hover_event
+                    );
+                }
+                let mut children = obj.extra.take().unwrap_or_default();
+                remove_inherited(obj, &mut children);
+                obj.extra = Some(children);
+            }
+        }
+    }
+
+    /// Sets `font` on this component and every object in its visible text, overriding any
+    /// fonts set further down.
+    ///
+    /// Unlike [`Self::font`], which only sets the root's font and lets children inherit it
+    /// unless they set their own, this forces one font on the whole message, e.g. to render
+    /// it with a resource pack's icon font. Fonts are resource locations in the form
+    /// `namespace:path` (such as `minecraft:uniform`); a missing namespace means `minecraft`.
+    /// Hover text is left alone.
+    #[must_use]
+    pub fn font_all(self, font: impl Into<String>) -> Self {
+        let font = font.into();
+        let mut root = self.font(Some(font.clone()));
+        root.visit_visible_objects_mut(&mut |obj| obj.font = Some(font.clone()));
+        root
+    }
+
+    /// Applies `f` to every object node in the tree, rebuilding it from the results.
+    ///
+    /// Plain strings are promoted to text objects first, so `f` sees every piece of text.
+    /// Arrays stay arrays, with their elements transformed. The traversal covers `extra`,
+    /// translation arguments, separators and hover text, and is post-order: when `f` receives
+    /// a node, its nested components have already been transformed.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let italic = Component::text("a")
+    ///     .append("b")
+    ///     .transform_objects(|mut obj| {
+    ///         obj.italic = Some(true);
+    ///         obj
+    ///     });
+    /// assert!(italic.get_children().iter().all(|c| c.has_decoration(TextDecoration::Italic)));
+    /// ```
+    #[must_use]
+    pub fn transform_objects<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(ComponentObject) -> ComponentObject,
+    {
+        self.transform_objects_with(&mut f)
+    }
+
+    fn transform_objects_with<F>(self, f: &mut F) -> Self
+    where
+        F: FnMut(ComponentObject) -> ComponentObject,
+    {
+        match self {
+            Component::String(text) => Component::Object(Box::new(f(ComponentObject {
+                text: Some(text),
+                ..Default::default()
+            }))),
+            Component::Array(children) => Component::Array(
+                children
+                    .into_iter()
+                    .map(|child| child.transform_objects_with(f))
+                    .collect(),
+            ),
+            Component::Object(mut obj) => {
+                for child in obj.nested_mut() {
+                    let taken = std::mem::replace(child, Component::String(String::new()));
+                    *child = taken.transform_objects_with(f);
+                }
+                Component::Object(Box::new(f(*obj)))
+            }
+        }
+    }
+
+    /// Calls `f` on this component and every component nested inside it, stopping as soon as
+    /// `f` returns [`ControlFlow::Break`].
+    ///
+    /// The traversal is pre-order and covers the same nodes as [`Self::nodes`]: a node is
+    /// visited before its children, which are visited after `f` returns, so they reflect
+    /// whatever `f` changed.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    /// use std::ops::ControlFlow;
+    ///
+    /// // Recolor the first node that has a hover event, and only that one
+    /// let mut message = Component::text("a ")
+    ///     .append(Component::text("b").hover_text("first"))
+    ///     .append(Component::text("c").hover_text("second"));
+    /// message.walk_mut(|node| match node {
+    ///     Component::Object(obj) if obj.hover_event.is_some() => {
+    ///         obj.color = Some(Color::Named(NamedColor::Aqua));
+    ///         ControlFlow::Break(())
+    ///     }
+    ///     _ => ControlFlow::Continue(()),
+    /// });
+    /// let aqua = Component::text("b")
+    ///     .hover_text("first")
+    ///     .color(Some(Color::Named(NamedColor::Aqua)));
+    /// assert_eq!(message.get_children()[0], aqua);
+    /// assert_eq!(message.get_children()[1], Component::text("c").hover_text("second"));
+    /// ```
+    pub fn walk_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Component) -> ControlFlow<()>,
+    {
+        let _ = self.walk_mut_with(&mut f);
+    }
+
+    fn walk_mut_with<F>(&mut self, f: &mut F) -> ControlFlow<()>
+    where
+        F: FnMut(&mut Component) -> ControlFlow<()>,
+    {
+        f(self)?;
+        match self {
+            Component::String(_) => {}
+            Component::Array(children) => {
+                for child in children {
+                    child.walk_mut_with(f)?;
+                }
+            }
+            Component::Object(obj) => {
+                for child in obj.nested_mut() {
+                    child.walk_mut_with(f)?;
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    /// Rewrites every color in the tree, including hover text, translation arguments and
+    /// separators. `f` receives each color that is set; returning `None` leaves it unchanged.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// // Swap every named color for its hex form
+    /// let message = Component::text("Hi")
+    ///     .color(Some(Color::Named(NamedColor::Red)))
+    ///     .map_colors(|color| match color {
+    ///         Color::Named(_) => Some(Color::Hex("#FF5555".into())),
+    ///         Color::Hex(_) => None,
+    ///     });
+    /// assert_eq!(message, Component::text("Hi").color(Some(Color::Hex("#FF5555".into()))));
+    /// ```
+    #[must_use]
+    pub fn map_colors<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Color) -> Option<Color>,
+    {
+        self.visit_objects_mut(&mut |obj| {
+            if let Some(color) = &obj.color
+                && let Some(new_color) = f(color)
+            {
+                obj.color = Some(new_color);
+            }
+        });
+        self
+    }
+
+    /// Removes every text and shadow color in the tree, including hover text, translation
+    /// arguments and separators, for monochrome displays or accessibility modes.
+    ///
+    /// Only colors go: decorations, fonts, insertion and events are kept, so the text still
+    /// reads and behaves the same, just in the default color.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("Warning")
+    ///     .color(Some(Color::Named(NamedColor::Red)))
+    ///     .decoration(TextDecoration::Bold, Some(true));
+    /// assert_eq!(
+    ///     message.strip_colors(),
+    ///     Component::text("Warning").decoration(TextDecoration::Bold, Some(true))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn strip_colors(mut self) -> Self {
+        self.visit_objects_mut(&mut |obj| {
+            obj.color = None;
+            obj.shadow_color = None;
+        });
+        self
+    }
+
+    /// Compares two components as they look, ignoring how they respond to the player: click
+    /// events, hover events (including their text) and insertion are skipped everywhere in
+    /// both trees. Everything else, text, content and every visual style property, has to
+    /// be equal, compared structurally like `==`.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let plain = Component::text("Shop");
+    /// let linked = Component::text("Shop").click_run("/shop").hover_text("Open the shop");
+    /// assert_ne!(plain, linked);
+    /// assert!(plain.equals_ignoring_events(&linked));
+    /// ```
+    pub fn equals_ignoring_events(&self, other: &Component) -> bool {
+        let strip = |component: &Component| {
+            let mut component = component.clone();
+            component.visit_objects_mut(&mut |obj| {
+                obj.click_event = None;
+                obj.hover_event = None;
+                obj.insertion = None;
+            });
+            component
+        };
+        strip(self) == strip(other)
+    }
+
+    /// Replaces every occurrence of the color `from` with `to` throughout the tree.
+    ///
+    /// Colors are compared exactly, so `Color::Named(NamedColor::Red)` does not match
+    /// `Color::Hex("#FF5555")`.
+    #[must_use]
+    pub fn replace_color(self, from: Color, to: Color) -> Self {
+        self.map_colors(|color| (*color == from).then(|| to.clone()))
+    }
+
+    /// Rewrites every click event in the tree, including those in hover text, translation
+    /// arguments and separators.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// // Namespace every command run from the message
+    /// let message = Component::text("Spawn")
+    ///     .click_event(Some(ClickEvent::RunCommand {
+    ///         command: "/spawn".into(),
+    ///     }))
+    ///     .map_click_events(|event| match event {
+    ///         ClickEvent::RunCommand { command } => ClickEvent::RunCommand {
+    ///             command: command.replacen('/', "/lobby:", 1),
+    ///         },
+    ///         other => other,
+    ///     });
+    /// assert_eq!(
+    ///     message,
+    ///     Component::text("Spawn").click_event(Some(ClickEvent::RunCommand {
+    ///         command: "/lobby:spawn".into(),
+    ///     }))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn map_click_events<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(ClickEvent) -> ClickEvent,
+    {
+        self.visit_objects_mut(&mut |obj| {
+            obj.click_event = obj.click_event.take().map(&mut f);
+        });
+        self
+    }
+
+    /// Rewrites every hover event in the tree, including those nested in hover text,
+    /// translation arguments and separators.
+    ///
+    /// Events nested in a hover event's text are rewritten before the hover event itself, and
+    /// the event returned by `f` isn't visited again.
+    #[must_use]
+    pub fn map_hover_events<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(HoverEvent) -> HoverEvent,
+    {
+        self.map_hover_events_with(&mut f)
+    }
+
+    fn map_hover_events_with<F>(self, f: &mut F) -> Self
+    where
+        F: FnMut(HoverEvent) -> HoverEvent,
+    {
+        match self.with_children_mapped(|child| child.map_hover_events_with(f)) {
+            Component::Object(mut obj) => {
+                obj.hover_event = obj.hover_event.take().map(&mut *f);
+                Component::Object(obj)
+            }
+            component => component,
+        }
+    }
+
+    /// Collects every run of visible text together with its fully inherited style, in reading
+    /// order. An array's first element is treated as the parent of the remaining elements.
+    pub(crate) fn styled_runs(&self) -> Vec<(&str, Style)> {
+        let mut runs = Vec::new();
+        self.collect_styled_runs(&Style::default(), &mut runs);
+        runs
+    }
+
+    fn collect_styled_runs<'a>(&'a self, parent: &Style, runs: &mut Vec<(&'a str, Style)>) {
+        match self {
+            Component::String(text) => runs.push((text, parent.clone())),
+            Component::Array(children) => {
+                let Some((first, rest)) = children.split_first() else {
+                    return;
+                };
+                first.collect_styled_runs(parent, runs);
+                let mut style = match first {
+                    Component::Object(obj) => obj.style(),
+                    _ => Style::default(),
+                };
+                style.inherit(parent);
+                for child in rest {
+                    child.collect_styled_runs(&style, runs);
+                }
+            }
+            Component::Object(obj) => {
+                let mut style = obj.style();
+                style.inherit(parent);
+                if let Some(text) = &obj.text {
+                    runs.push((text, style.clone()));
+                }
+                for child in obj.extra.iter().flatten() {
+                    child.collect_styled_runs(&style, runs);
+                }
+            }
+        }
+    }
+
+    /// Iterates over the runs of visible text in reading order, each with its effective
+    /// style, borrowing the text from the tree.
+    ///
+    /// The style of a run is its own style with anything left unset inherited from its
+    /// parents: an object's children inherit from the object, and the elements of an array
+    /// after the first inherit from the first one, the way Minecraft renders them. Empty runs
+    /// are skipped.
+    ///
+    /// Only `text` content is yielded. Translations, scores, selectors, keybinds and NBT are
+    /// resolved by the client, so their content is skipped, though text in their `extra` is
+    /// still yielded. Hover text isn't visible text and is skipped too.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("Hello ")
+    ///     .decoration(TextDecoration::Bold, Some(true))
+    ///     .append("world");
+    /// let words: Vec<_> = message
+    ///     .iter_text()
+    ///     .flat_map(|(text, _)| text.split_whitespace())
+    ///     .collect();
+    /// assert_eq!(words, ["Hello", "world"]);
+    /// assert!(message.iter_text().all(|(_, style)| style.bold == Some(true)));
+    /// ```
+    pub fn iter_text(&self) -> impl Iterator<Item = (&str, Style)> {
+        self.styled_runs()
+            .into_iter()
+            .filter(|(text, _)| !text.is_empty())
+    }
+
+    /// Flattens this component into runs of text with their fully resolved style, in reading
+    /// order.
+    ///
+    /// This is the form UI renderers work with: each span can be drawn on its own without
+    /// looking at the tree. Only `text` content is included, and runs without text are
+    /// skipped.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let red = Some(Color::Named(NamedColor::Red));
+    /// let message = Component::text("Hi ").color(red.clone()).append("there");
+    /// let spans = message.to_spans();
+    /// assert_eq!(spans[1].text, "there");
+    /// assert_eq!(spans[1].style.color, red);
+    /// ```
+    pub fn to_spans(&self) -> Vec<StyledSpan> {
+        self.iter_text()
+            .map(|(text, style)| StyledSpan {
+                text: text.to_string(),
+                style,
+            })
+            .collect()
+    }
+
+    /// Returns a pre-order iterator over this component and every component nested inside it,
+    /// including translation arguments, separators and hover text.
+    pub fn nodes(&self) -> Nodes<'_> {
+        Nodes { stack: vec![self] }
+    }
+
+    /// Checks whether the resolved plain text (see [`Self::to_plain_text`]) contains `needle`.
+    ///
+    /// Only the visible text is searched; hover text is not part of the plain text.
+    pub fn contains_text(&self, needle: &str) -> bool {
+        self.to_plain_text().contains(needle)
+    }
+
+    /// Returns every node whose own text contains `needle`, in traversal order.
+    ///
+    /// Unlike [`Self::contains_text`], this searches hover text and translation arguments too,
+    /// and the returned nodes carry their styling as context. A match spanning two sibling
+    /// nodes is not found.
+    pub fn find_text(&self, needle: &str) -> Vec<&Component> {
+        self.nodes()
+            .filter(|node| {
+                node.get_plain_text()
+                    .is_some_and(|text| text.contains(needle))
+            })
+            .collect()
+    }
+
+    /// Returns every object node matching `pred`, in traversal order.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("Click ").append(
+    ///     Component::text("here").click_event(Some(ClickEvent::RunCommand {
+    ///         command: "/spawn".into(),
+    ///     })),
+    /// );
+    /// let commands = message.find_nodes(|obj| {
+    ///     matches!(obj.click_event, Some(ClickEvent::RunCommand { .. }))
+    /// });
+    /// assert_eq!(commands.len(), 1);
+    /// ```
+    pub fn find_nodes<F>(&self, pred: F) -> Vec<&ComponentObject>
+    where
+        F: Fn(&ComponentObject) -> bool,
+    {
+        self.nodes()
+            .filter_map(|node| match node {
+                Component::Object(obj) if pred(obj) => Some(&**obj),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Collects the `translate` key of every translatable component in the tree, including
+    /// translation arguments, hover text and separators, e.g. to check that a set of messages
+    /// only uses keys present in a language file.
+    ///
+    /// Keys are returned in traversal order (see [`Self::nodes`]) and duplicates are kept, so
+    /// sort and deduplicate the result if needed.
+    ///
+    /// ```
+    /// use kyori_component_json::Component;
+    ///
+    /// let message = Component::translatable("chat.type.announcement")
+    ///     .with_arg(Component::translatable("commands.server"))
+    ///     .with_arg(Component::translatable("multiplayer.player.joined").with_text_arg("Steve"));
+    /// assert_eq!(
+    ///     message.translation_keys(),
+    ///     ["chat.type.announcement", "commands.server", "multiplayer.player.joined"]
+    /// );
+    /// ```
+    pub fn translation_keys(&self) -> Vec<&str> {
+        self.nodes()
+            .filter_map(|node| match node {
+                Component::Object(obj) => obj.translate.as_deref(),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Unsets the style properties of `children` that are equal to the ones `parent` sets, since
+/// they would inherit them anyway
+fn remove_inherited(parent: &ComponentObject, children: &mut [Component]) {
+    for child in children.iter_mut().filter_map(Component::as_object_mut) {
+        macro_rules! remove {
+            ($($field:ident),*) => {$(
+                if parent.$field.is_some() && child.$field == parent.$field {
+                    child.$field = None;
+                }
+            )*};
+        }
+        remove!(
+            color,
+            font,
+            bold,
+            italic,
+            underlined,
+            strikethrough,
+            obfuscated,
+            shadow_color,
+            insertion,
+            click_event,
+            hover_event
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn sample() -> Component {
+        Component::text("Hello ").append(
+            Component::text("world")
+                .hover_event(Some(HoverEvent::ShowText {
+                    value: Component::text("secret ").append(Component::text("needle")),
+                }))
+                .append(Component::String("!".to_string())),
+        )
+    }
+
+    #[test]
+    fn test_to_spans_resolves_inherited_styles() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let blue = Some(Color::Named(NamedColor::Blue));
+        let click = Some(ClickEvent::RunCommand {
+            command: "/spawn".into(),
+        });
+        let message = Component::text("")
+            .color(red.clone())
+            .click_event(click.clone())
+            .append(Component::text("a").append(Component::text("b").color(blue.clone())))
+            .append("c");
+
+        let spans: Vec<_> = message
+            .to_spans()
+            .into_iter()
+            .map(|span| (span.text, span.style.color, span.style.click_event))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("a".to_string(), red.clone(), click.clone()),
+                ("b".to_string(), blue, click.clone()),
+                ("c".to_string(), red, click),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_array_parent() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let array = Component::Array(vec![
+            Component::text("a").color(red.clone()).append("x"),
+            Component::from("b"),
+            Component::text("c").hover_text(Component::Array(vec!["d".into(), "e".into()])),
+        ]);
+        let colors = |c: &Component| {
+            c.styled_runs()
+                .into_iter()
+                .map(|(text, style)| (text.to_string(), style.color))
+                .collect::<Vec<_>>()
+        };
+        let before = colors(&array);
+        let resolved = array.clone().resolve_array_parent();
+        assert_eq!(
+            resolved,
+            Component::text("a").color(red.clone()).set_children(vec![
+                Component::from("x"),
+                Component::from("b"),
+                Component::text("c").hover_text(Component::from("d").append("e")),
+            ])
+        );
+        // The first element's style still reaches the others
+        assert_eq!(colors(&resolved), before);
+        assert_eq!(colors(&resolved)[2], ("b".to_string(), red));
+
+        assert_eq!(
+            Component::Array(Vec::new()).resolve_array_parent(),
+            Component::text("")
+        );
+    }
+
+    #[test]
+    fn test_map_click_events_rewrites_every_command() {
+        let run = |command: &str| {
+            Some(ClickEvent::RunCommand {
+                command: command.into(),
+            })
+        };
+        let url = Some(ClickEvent::OpenUrl {
+            url: "https://example.com".into(),
+        });
+        let message = Component::text("a")
+            .click_event(run("/a"))
+            .hover_text(Component::text("tip").click_event(run("/tip")))
+            .append(Component::text("b").click_event(url.clone()))
+            .append(Component::Array(vec![
+                Component::text("c").click_event(run("/c")),
+            ]));
+
+        let mut seen = 0;
+        let rewritten = message.map_click_events(|event| {
+            seen += 1;
+            match event {
+                ClickEvent::RunCommand { command } => ClickEvent::RunCommand {
+                    command: format!("/ns:{}", &command[1..]),
+                },
+                other => other,
+            }
+        });
+        assert_eq!(seen, 4);
+        assert_eq!(
+            rewritten,
+            Component::text("a")
+                .click_event(run("/ns:a"))
+                .hover_text(Component::text("tip").click_event(run("/ns:tip")))
+                .append(Component::text("b").click_event(url))
+                .append(Component::Array(vec![
+                    Component::text("c").click_event(run("/ns:c"))
+                ]))
+        );
+    }
+
+    #[test]
+    fn test_map_hover_events_visits_nested_hover_text() {
+        let message = Component::text("a")
+            .hover_text(Component::text("outer").hover_text("inner"))
+            .append(Component::text("b").hover_text("child"));
+        let rewritten = message.map_hover_events(|event| match event {
+            HoverEvent::ShowText { value } => HoverEvent::ShowText {
+                value: Component::text("> ").append(value),
+            },
+            other => other,
+        });
+        assert_eq!(
+            rewritten,
+            Component::text("a")
+                .hover_text(Component::text("> ").append(
+                    Component::text("outer").hover_text(Component::text("> ").append("inner"))
+                ))
+                .append(Component::text("b").hover_text(Component::text("> ").append("child")))
+        );
+    }
+
+    #[test]
+    fn test_strip_colors_keeps_decorations_and_events() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let click = Some(ClickEvent::SuggestCommand {
+            command: "/msg ".into(),
+        });
+        let message = Component::text("a")
+            .color(red.clone())
+            .map_object(|mut obj| {
+                obj.shadow_color = Some(ShadowColor::Int(-16777216));
+                obj
+            })
+            .decoration(TextDecoration::Italic, Some(true))
+            .click_event(click.clone())
+            .hover_text(Component::text("tip").color(red.clone()))
+            .append(Component::text("b").color(Some(Color::Hex("#123456".into()))));
+
+        let stripped = message.strip_colors();
+        assert_eq!(
+            stripped,
+            Component::text("a")
+                .decoration(TextDecoration::Italic, Some(true))
+                .click_event(click)
+                .hover_text(Component::text("tip"))
+                .append(Component::text("b"))
+        );
+        assert_eq!(stripped, "ab");
+    }
+
+    #[test]
+    fn test_equals_ignoring_events() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let a = Component::text("x")
+            .color(red.clone())
+            .click_run("/a")
+            .append(Component::text("y").insertion(Some("ins".into())));
+        let b = Component::text("x")
+            .color(red)
+            .click_url("https://example.com")
+            .hover_text("tip")
+            .append(Component::text("y"));
+        assert_ne!(a, b);
+        assert!(a.equals_ignoring_events(&b));
+
+        // Visual differences still count
+        let c = Component::text("x").click_run("/a").append("y");
+        assert!(!a.equals_ignoring_events(&c));
+        assert!(!a.equals_ignoring_events(&Component::text("x")));
+    }
+
+    #[test]
+    fn test_iter_text_inherits_styles() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let blue = Some(Color::Named(NamedColor::Blue));
+        let message = Component::Array(vec![
+            Component::text("Hi ").color(red.clone()),
+            Component::text("there")
+                .decoration(TextDecoration::Italic, Some(true))
+                .hover_text("tip")
+                .append(Component::text("!").color(blue.clone())),
+            Component::translatable("chat.type.text").append(""),
+            Component::from(" bye"),
+        ]);
+
+        let runs: Vec<_> = message
+            .iter_text()
+            .map(|(text, style)| (text, style.color, style.italic))
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                ("Hi ", red.clone(), None),
+                ("there", red.clone(), Some(true)),
+                ("!", blue, Some(true)),
+                (" bye", red, None),
+            ]
+        );
+        // The text is borrowed from the tree
+        let Some((first, _)) = message.iter_text().next() else {
+            panic!("expected a run");
+        };
+        assert_eq!(
+            first.as_ptr(),
+            message.as_array().unwrap()[0]
+                .as_object()
+                .unwrap()
+                .text
+                .as_ref()
+                .unwrap()
+                .as_ptr()
+        );
+    }
+
+    #[test]
+    fn test_translation_keys() {
+        let item = Component::translatable("item.minecraft.diamond");
+        let message = Component::text("Got ")
+            .append(
+                Component::translatable("chat.square_brackets")
+                    .with_arg(item.clone())
+                    .hover_text(Component::text("x").append(item.clone())),
+            )
+            .append(Component::Array(vec![
+                "".into(),
+                Component::translatable("chat.type.text")
+                    .with_text_arg("a")
+                    .with_arg(Component::translatable("gui.done")),
+            ]));
+
+        assert_eq!(
+            message.translation_keys(),
+            [
+                "chat.square_brackets",
+                "item.minecraft.diamond",
+                "item.minecraft.diamond",
+                "chat.type.text",
+                "gui.done",
+            ]
+        );
+        assert!(Component::text("plain").translation_keys().is_empty());
+    }
+
+    #[test]
+    fn test_nodes_pre_order() {
+        let texts: Vec<_> = sample()
+            .nodes()
+            .filter_map(|node| node.get_plain_text().map(str::to_string))
+            .collect();
+        assert_eq!(texts, ["Hello ", "world", "secret ", "needle", "!"]);
+    }
+
+    #[test]
+    fn test_walk_mut_stops_at_break() {
+        let mut message = sample();
+        let mut visited = Vec::new();
+        message.walk_mut(|node| {
+            let text = node.get_plain_text().unwrap_or_default().to_string();
+            if text == "secret "
+                && let Component::Object(obj) = node
+            {
+                obj.text = Some("found".to_string());
+                return std::ops::ControlFlow::Break(());
+            }
+            visited.push(text);
+            std::ops::ControlFlow::Continue(())
+        });
+        // Same order as `nodes`, and nothing after the break is visited
+        assert_eq!(visited, ["Hello ", "world"]);
+        assert_eq!(message.find_text("found").len(), 1);
+        assert_eq!(message.find_text("needle").len(), 1);
+    }
+
+    #[test]
+    fn test_contains_and_find_text() {
+        let message = sample();
+        assert!(message.contains_text("o w"));
+        // Hover text is not visible, so it isn't part of the plain text
+        assert!(!message.contains_text("needle"));
+
+        let found = message.find_text("needle");
+        assert_eq!(found, [&Component::text("needle")]);
+        assert!(message.find_text("missing").is_empty());
+    }
+
+    #[test]
+    fn test_replace_color() {
+        let red = || Some(Color::Named(NamedColor::Red));
+        let gold = || Some(Color::Named(NamedColor::Gold));
+        let message = Component::text("a")
+            .color(red())
+            .append(
+                Component::text("b")
+                    .color(Some(Color::Named(NamedColor::Blue)))
+                    .hover_text(Component::text("c").color(red())),
+            )
+            .append(Component::text("d").color(red()));
+
+        let expected = Component::text("a")
+            .color(gold())
+            .append(
+                Component::text("b")
+                    .color(Some(Color::Named(NamedColor::Blue)))
+                    .hover_text(Component::text("c").color(gold())),
+            )
+            .append(Component::text("d").color(gold()));
+
+        assert_eq!(
+            message.replace_color(
+                Color::Named(NamedColor::Red),
+                Color::Named(NamedColor::Gold)
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_transform_objects_toggles_bold() {
+        let message = Component::text("a")
+            .decoration(TextDecoration::Bold, Some(true))
+            .append(Component::String("b".to_string()))
+            .hover_text(Component::text("c"));
+
+        let toggled = message.transform_objects(|mut obj| {
+            obj.bold = Some(!obj.bold.unwrap_or(false));
+            obj
+        });
+
+        let expected = Component::text("a")
+            .decoration(TextDecoration::Bold, Some(false))
+            .append(Component::text("b").decoration(TextDecoration::Bold, Some(true)))
+            .hover_text(Component::text("c").decoration(TextDecoration::Bold, Some(true)));
+        assert_eq!(toggled, expected);
+    }
+
+    #[test]
+    fn test_hoist_common_styles() {
+        let red = Some(Color::Named(NamedColor::Red));
+        let bold = |c: Component| c.decoration(TextDecoration::Bold, Some(true));
+
+        // Array elements inherit the first element's style
+        let array = Component::Array(vec![
+            bold(Component::text("a").color(red.clone())),
+            Component::text("b").color(red.clone()),
+            bold(Component::text("c").color(red.clone())),
+        ]);
+        let hoisted = array.clone().hoist_common_styles();
+        assert_eq!(
+            hoisted,
+            Component::Array(vec![
+                bold(Component::text("a").color(red.clone())),
+                Component::text("b"),
+                Component::text("c"),
+            ])
+        );
+        assert!(hoisted.iter_text().eq(array.iter_text()));
+
+        // Nested lists hoist level by level, and the shared click event moves up too
+        let item = |text: &str| {
+            Component::text(text)
+                .color(red.clone())
+                .click_suggest("/msg ")
+        };
+        let nested = Component::text("").set_children(vec![
+            Component::text("").set_children(vec![item("a"), bold(item("b"))]),
+            Component::text("").set_children(vec![item("c"), item("d")]),
+        ]);
+        let hoisted = nested.clone().hoist_common_styles();
+        assert_eq!(
+            hoisted,
+            Component::text("")
+                .color(red.clone())
+                .click_suggest("/msg ")
+                .append(
+                    Component::text("")
+                        .set_children(vec![Component::text("a"), bold(Component::text("b")),])
+                )
+                .append(
+                    Component::text("")
+                        .set_children(vec![Component::text("c"), Component::text("d"),])
+                )
+        );
+        assert!(hoisted.iter_text().eq(nested.iter_text()));
+        assert!(
+            hoisted.estimate_json_size() < nested.estimate_json_size(),
+            "hoisting should shrink the JSON"
+        );
+
+        // Nothing moves onto the first element of an array, as the rest would inherit it
+        let array = Component::Array(vec![
+            Component::text("").set_children(vec![item("a"), item("b")]),
+            Component::text("c"),
+        ]);
+        let hoisted = array.clone().hoist_common_styles();
+        assert_eq!(hoisted, array);
+        assert!(hoisted.iter_text().eq(array.iter_text()));
+
+        // Nothing moves onto a parent showing text, or past a child that sets nothing
+        let blocked = [
+            Component::text("parent").set_children(vec![item("a"), item("b")]),
+            Component::text("").set_children(vec![item("a"), "b".into()]),
+        ];
+        for component in blocked {
+            assert_eq!(component.clone().hoist_common_styles(), component);
+        }
+    }
+
+    #[test]
+    fn test_flatten_styles_cascades_font() {
+        let font = || Some("minecraft:uniform".to_string());
+        let message = Component::text("a")
+            .font(font())
+            .color(Some(Color::Named(NamedColor::Red)))
+            .append(Component::text("b").append("c"))
+            .append(Component::text("d").font(Some("minecraft:alt".into())));
+
+        let flat = message.clone().flatten_styles();
+        let b = &flat.get_children()[0];
+        assert!(matches!(b, Component::Object(obj) if obj.font == font()));
+        let c = &b.get_children()[0];
+        assert!(matches!(c, Component::Object(obj) if obj.font == font()));
+        let d = &flat.get_children()[1];
+        assert!(
+            matches!(d, Component::Object(obj) if obj.font.as_deref() == Some("minecraft:alt"))
+        );
+        // Flattening doesn't change what is rendered
+        assert_eq!(flat.styled_runs(), message.styled_runs());
+
+        let array = Component::Array(vec![Component::text("x").font(font()), "y".into()]);
+        assert_eq!(
+            array.flatten_styles(),
+            Component::Array(vec![
+                Component::text("x").font(font()),
+                Component::text("y").font(font())
+            ])
+        );
+    }
+
+    #[test]
+    fn test_flatten_styles_descends_into_args_and_hover() {
+        let red = || Some(Color::Named(NamedColor::Red));
+        let translatable = ComponentObject {
+            translate: Some("chat.type.text".into()),
+            with: Some(vec![Component::from("arg")]),
+            hover_event: Some(HoverEvent::ShowText {
+                value: Component::text("tip")
+                    .decoration(TextDecoration::Bold, Some(true))
+                    .append("!"),
+            }),
+            ..Default::default()
+        };
+        let message = Component::text("")
+            .color(red())
+            .append(Component::Object(Box::new(translatable)));
+
+        let flat = message.flatten_styles();
+        let Component::Object(obj) = &flat.get_children()[0] else {
+            panic!("expected an object");
+        };
+        assert!(matches!(
+            obj.with.as_deref(),
+            Some([Component::Object(arg)]) if arg.color == red() && arg.hover_event == obj.hover_event
+        ));
+        // Hover text doesn't inherit the message's color, but is flattened on its own
+        assert_eq!(
+            obj.hover_event,
+            Some(HoverEvent::ShowText {
+                value: Component::text("tip")
+                    .decoration(TextDecoration::Bold, Some(true))
+                    .append(Component::text("!").decoration(TextDecoration::Bold, Some(true))),
+            })
+        );
+    }
+
+    #[test]
+    fn test_font_all_overrides_nested_fonts() {
+        let message = Component::text("a")
+            .append(Component::text("b").font(Some("minecraft:alt".into())))
+            .append("c")
+            .hover_text("hover");
+        let icons = message.font_all("pack:icons");
+        assert!(icons.nodes().all(|node| match node {
+            Component::Object(obj) if obj.text.as_deref() == Some("hover") => obj.font.is_none(),
+            Component::Object(obj) => obj.font.as_deref() == Some("pack:icons"),
+            _ => true,
+        }));
+        assert_eq!(
+            icons.get_children()[0],
+            Component::text("b").font(Some("pack:icons".into()))
+        );
+    }
+
+    #[test]
+    fn test_find_nodes() {
+        let message = sample();
+        let hovered = message.find_nodes(|obj| obj.hover_event.is_some());
+        assert_eq!(hovered.len(), 1);
+        assert_eq!(hovered[0].text.as_deref(), Some("world"));
+    }
+}
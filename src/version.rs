@@ -0,0 +1,342 @@
+//! Adapting components to older Minecraft versions.
+//!
+//! Features unsupported by a version are dropped or replaced by [`Component::downgrade`]:
+//!
+//! | Feature                           | Since  | Older versions                |
+//! |-----------------------------------|--------|-------------------------------|
+//! | `copy_to_clipboard` click event   | 1.15   | removed                       |
+//! | Hex colors                        | 1.16   | replaced by the nearest named |
+//! | `font`                            | 1.16   | removed                       |
+//! | `separator`                       | 1.17   | removed                       |
+//! | Translation `fallback`            | 1.19.4 | removed                       |
+//! | Explicit content `type`           | 1.20.3 | removed                       |
+//! | `shadow_color`                    | 1.21.4 | removed                       |
+//! | `show_dialog` and `custom` clicks | 1.21.6 | removed                       |
+//!
+//! A [`Component`] always serializes with the `click_event` and `hover_event` keys used since
+//! 1.21.5, which older clients ignore. [`Component::to_json_for`] writes the older
+//! `clickEvent` and `hoverEvent` form for them.
+
+use crate::{ClickEvent, Color, Component, ComponentObject};
+use serde_json::{Map, Value};
+
+/// A Minecraft Java Edition release, `1.<minor>.<patch>`.
+///
+/// Versions compare in release order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct McVersion {
+    /// Minor version, e.g. `21` in 1.21.4
+    pub minor: u16,
+    /// Patch version, e.g. `4` in 1.21.4
+    pub patch: u16,
+}
+
+impl McVersion {
+    /// Minecraft 1.12
+    pub const V1_12: McVersion = McVersion::new(12, 0);
+    /// Minecraft 1.15, which added the `copy_to_clipboard` click event
+    pub const V1_15: McVersion = McVersion::new(15, 0);
+    /// Minecraft 1.16, which added hex colors and fonts
+    pub const V1_16: McVersion = McVersion::new(16, 0);
+    /// Minecraft 1.17, which added separators
+    pub const V1_17: McVersion = McVersion::new(17, 0);
+    /// Minecraft 1.19.4, which added translation fallbacks
+    pub const V1_19_4: McVersion = McVersion::new(19, 4);
+    /// Minecraft 1.20.3, which added the explicit content `type`
+    pub const V1_20_3: McVersion = McVersion::new(20, 3);
+    /// Minecraft 1.21.4, which added shadow colors
+    pub const V1_21_4: McVersion = McVersion::new(21, 4);
+    /// Minecraft 1.21.5, which renamed `clickEvent` and `hoverEvent` to `click_event` and
+    /// `hover_event` and moved their values into named fields
+    pub const V1_21_5: McVersion = McVersion::new(21, 5);
+    /// Minecraft 1.21.6, which added the `show_dialog` and `custom` click events
+    pub const V1_21_6: McVersion = McVersion::new(21, 6);
+
+    /// Creates the version `1.<minor>.<patch>`
+    pub const fn new(minor: u16, patch: u16) -> Self {
+        McVersion { minor, patch }
+    }
+}
+
+impl ComponentObject {
+    /// Drops or replaces the properties of this object that `version` doesn't support
+    fn downgrade(&mut self, version: McVersion) {
+        if version < McVersion::V1_15
+            && matches!(self.click_event, Some(ClickEvent::CopyToClipboard { .. }))
+        {
+            self.click_event = None;
+        }
+        if version < McVersion::V1_16 {
+            if let Some(color @ Color::Hex(_)) = &self.color {
+                self.color = color.to_nearest_named().map(Color::Named);
+            }
+            self.font = None;
+        }
+        if version < McVersion::V1_17 {
+            self.separator = None;
+        }
+        if version < McVersion::V1_19_4 {
+            self.fallback = None;
+        }
+        if version < McVersion::V1_20_3 {
+            self.content_type = None;
+        }
+        if version < McVersion::V1_21_4 {
+            self.shadow_color = None;
+        }
+        if version < McVersion::V1_21_6
+            && matches!(
+                self.click_event,
+                Some(ClickEvent::ShowDialog { .. } | ClickEvent::Custom { .. })
+            )
+        {
+            self.click_event = None;
+        }
+    }
+}
+
+impl Component {
+    /// Makes this component displayable by `version`, removing or converting the features it
+    /// doesn't support. See the [table](crate::version) of what changes per version.
+    ///
+    /// Every nested component is downgraded too, hover text included.
+    ///
+    /// The result is still a [`Component`], which serializes click and hover events with the
+    /// keys used since 1.21.5. Clients before 1.21.5 ignore them, so use
+    /// [`Self::to_json_for`] to get JSON they read.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let message = Component::text("Hi")
+    ///     .color(Some(Color::Hex("#FF5050".into())))
+    ///     .font(Some("minecraft:uniform".into()));
+    /// assert_eq!(
+    ///     message.downgrade(McVersion::V1_12),
+    ///     Component::text("Hi").color(Some(Color::Named(NamedColor::Red)))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn downgrade(mut self, version: McVersion) -> Self {
+        self.visit_objects_mut(&mut |obj| obj.downgrade(version));
+        self
+    }
+
+    /// Downgrades this component for `version` and serializes it to a JSON value that version
+    /// reads.
+    ///
+    /// Below 1.21.5, click and hover events are written in their older form: `clickEvent`
+    /// with a single `value`, and `hoverEvent` with the shown text, item or entity under
+    /// `contents`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component fails to serialize.
+    pub fn to_value_for(&self, version: McVersion) -> Result<Value, serde_json::Error> {
+        let mut value = serde_json::to_value(self.clone().downgrade(version))?;
+        if version < McVersion::V1_21_5 {
+            to_legacy_events(&mut value);
+        }
+        Ok(value)
+    }
+
+    /// Like [`Self::to_value_for`], but serialized to a JSON string.
+    ///
+    /// ```
+    /// use kyori_component_json::*;
+    ///
+    /// let link = Component::text("Wiki").click_url("https://minecraft.wiki");
+    /// let json = link.to_json_for(McVersion::V1_20_3).unwrap();
+    /// assert!(json.contains(r#""clickEvent":{"action":"open_url","value":"https://minecraft.wiki"}"#));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component fails to serialize.
+    pub fn to_json_for(&self, version: McVersion) -> Result<String, serde_json::Error> {
+        self.to_value_for(version)
+            .and_then(|value| serde_json::to_string(&value))
+    }
+}
+
+/// Rewrites the click and hover events of serialized component JSON into the form used
+/// before 1.21.5. Click events without an older form are removed.
+pub(crate) fn to_legacy_events(value: &mut Value) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(to_legacy_events),
+        Value::Object(obj) => {
+            for key in ["extra", "with"] {
+                if let Some(Value::Array(items)) = obj.get_mut(key) {
+                    items.iter_mut().for_each(to_legacy_events);
+                }
+            }
+            if let Some(separator) = obj.get_mut("separator") {
+                to_legacy_events(separator);
+            }
+            if let Some(Value::Object(click)) = obj.remove("click_event")
+                && let Some(click) = legacy_click_event(click)
+            {
+                obj.insert("clickEvent".into(), click);
+            }
+            if let Some(Value::Object(hover)) = obj.remove("hover_event") {
+                obj.insert("hoverEvent".into(), legacy_hover_event(hover));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `{"action":...,"value":...}`, or `None` for actions that didn't exist before 1.21.5
+fn legacy_click_event(mut click: Map<String, Value>) -> Option<Value> {
+    let action = click.remove("action")?;
+    let key = match action.as_str()? {
+        "open_url" => "url",
+        "open_file" => "path",
+        "run_command" | "suggest_command" => "command",
+        "change_page" => "page",
+        "copy_to_clipboard" => "value",
+        _ => return None,
+    };
+    let value = match click.remove(key)? {
+        // Pages were strings too
+        Value::Number(page) => Value::String(page.to_string()),
+        value => value,
+    };
+    Some(serde_json::json!({ "action": action, "value": value }))
+}
+
+/// `{"action":...,"contents":...}`, with entity fields renamed to their older names
+fn legacy_hover_event(mut hover: Map<String, Value>) -> Value {
+    let action = hover.remove("action").unwrap_or_default();
+    let contents = match action.as_str() {
+        Some("show_text") => {
+            let mut text = hover.remove("value").unwrap_or_default();
+            to_legacy_events(&mut text);
+            text
+        }
+        Some("show_entity") => {
+            let mut entity = Map::new();
+            if let Some(id) = hover.remove("id") {
+                entity.insert("type".into(), id);
+            }
+            if let Some(uuid) = hover.remove("uuid") {
+                entity.insert("id".into(), uuid);
+            }
+            if let Some(mut name) = hover.remove("name") {
+                to_legacy_events(&mut name);
+                entity.insert("name".into(), name);
+            }
+            Value::Object(entity)
+        }
+        _ => Value::Object(hover),
+    };
+    serde_json::json!({ "action": action, "contents": contents })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_downgrade_to_1_12() {
+        let modern = Component::Object(Box::new(ComponentObject {
+            content_type: Some(ContentType::Translatable),
+            translate: Some("custom.greeting".into()),
+            fallback: Some("Hello %s".into()),
+            with: Some(vec![
+                Component::text("Steve").color(Some(Color::Hex("#0000A0".into()))),
+            ]),
+            shadow_color: Some(ShadowColor::Int(-16777216)),
+            font: Some("minecraft:alt".into()),
+            click_event: Some(ClickEvent::CopyToClipboard {
+                value: "copied".into(),
+            }),
+            hover_event: Some(HoverEvent::ShowText {
+                value: Component::text("tip").color(Some(Color::Hex("#FEFEFE".into()))),
+            }),
+            ..Default::default()
+        }));
+
+        let old = Component::Object(Box::new(ComponentObject {
+            translate: Some("custom.greeting".into()),
+            with: Some(vec![
+                Component::text("Steve").color(Some(Color::Named(NamedColor::DarkBlue))),
+            ]),
+            hover_event: Some(HoverEvent::ShowText {
+                value: Component::text("tip").color(Some(Color::Named(NamedColor::White))),
+            }),
+            ..Default::default()
+        }));
+        assert_eq!(modern.clone().downgrade(McVersion::V1_12), old);
+
+        // A recent enough version keeps everything
+        assert_eq!(
+            modern.clone().downgrade(McVersion::new(21, 5)),
+            modern.clone()
+        );
+
+        // Between the two, only what's missing is removed
+        let Component::Object(obj) = modern.downgrade(McVersion::V1_16) else {
+            panic!("expected an object");
+        };
+        assert!(obj.font.is_some() && obj.click_event.is_some());
+        assert!(obj.fallback.is_none() && obj.shadow_color.is_none());
+    }
+
+    #[test]
+    fn test_legacy_event_keys() {
+        let message = Component::text("Shop")
+            .click_event(Some(ClickEvent::ChangePage { page: 2 }))
+            .hover_text(Component::text("tip").click_run("/tip"))
+            .append(Component::text("dialog").click_show_dialog("minecraft:server_links"))
+            .append(
+                Component::text("mob").hover_event(Some(HoverEvent::ShowEntity {
+                    name: Some(Component::text("Bob")),
+                    id: "minecraft:pig".into(),
+                    uuid: UuidRepr::String("00000000-0000-0000-0000-000000000001".into()),
+                })),
+            );
+
+        let legacy = message.to_value_for(McVersion::V1_20_3).unwrap();
+        assert_eq!(
+            legacy,
+            serde_json::json!({
+                "text": "Shop",
+                "clickEvent": {"action": "change_page", "value": "2"},
+                "hoverEvent": {
+                    "action": "show_text",
+                    "contents": {
+                        "text": "tip",
+                        "clickEvent": {"action": "run_command", "value": "/tip"}
+                    }
+                },
+                "extra": [
+                    {"text": "dialog"},
+                    {
+                        "text": "mob",
+                        "hoverEvent": {
+                            "action": "show_entity",
+                            "contents": {
+                                "type": "minecraft:pig",
+                                "id": "00000000-0000-0000-0000-000000000001",
+                                "name": {"text": "Bob"}
+                            }
+                        }
+                    }
+                ]
+            })
+        );
+
+        // Downgrading alone keeps the current keys, which old clients ignore
+        let downgraded =
+            serde_json::to_value(message.clone().downgrade(McVersion::V1_20_3)).unwrap();
+        assert!(downgraded.get("click_event").is_some());
+        assert!(downgraded.get("clickEvent").is_none());
+
+        // 1.21.5 and later read the current form
+        assert_eq!(
+            message.to_value_for(McVersion::V1_21_5).unwrap(),
+            serde_json::to_value(message.downgrade(McVersion::V1_21_5)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,5 @@
+use kyori_component_json::component;
+
+fn main() {
+    let _ = component!(text: "Hello", { color: yelow });
+}